@@ -0,0 +1,71 @@
+//! Generates the table of solutions that `cargo all-fast` runs in-process.
+//! With the `in_process` feature, every `src/bin/NN.rs` and `src/bin/YYYY-NN.rs` file is included into the `advent_of_code` binary as a module.
+//! Without it the table is empty, so the other commands keep working while a solution does not compile.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let is_in_process = env::var_os("CARGO_FEATURE_IN_PROCESS").is_some();
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    let stem = path.file_stem()?.to_str()?;
+                    let is_rs = path.extension()? == "rs";
+                    let name = parse_bin_name(stem)?;
+                    is_rs.then(|| (name, path.to_str().unwrap().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    // without the feature, the solutions don't have to compile for the binary to build.
+    if !is_in_process {
+        days.clear();
+    }
+
+    days.sort_unstable();
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    for (name, path) in &days {
+        modules.push_str(&format!(
+            "#[cfg(not(test))]\n#[path = {path:?}]\nmod day_{name};\n"
+        ));
+        entries.push_str(&format!("day_{name}::SOLUTION, "));
+    }
+
+    let contents = format!(
+        "{modules}
+#[cfg(not(test))]
+pub static SOLUTIONS: &[&dyn advent_of_code::template::registry::Solution] = &[{entries}];
+
+#[cfg(test)]
+pub static SOLUTIONS: &[&dyn advent_of_code::template::registry::Solution] = &[];
+"
+    );
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, contents).unwrap();
+}
+
+/// Turns a bin name (`NN` or `YYYY-NN`) into a module name suffix (`NN` or `YYYY_NN`).
+fn parse_bin_name(stem: &str) -> Option<String> {
+    let is_day = |s: &str| s.len() == 2 && s.parse::<u8>().is_ok_and(|d| (1..=25).contains(&d));
+
+    match stem.split_once('-') {
+        Some((year, day)) => {
+            let is_year = year.len() == 4 && year.parse::<u16>().is_ok();
+            (is_year && is_day(day)).then(|| format!("{year}_{day}"))
+        }
+        None => is_day(stem).then(|| stem.to_string()),
+    }
+}
@@ -0,0 +1,225 @@
+use std::fmt::{self, Display};
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The answer to a puzzle part.
+///
+/// Solutions can return any type that converts into an [`Answer`], e.g. integers or strings.
+/// Strings that span multiple lines are treated as grids, e.g. letters drawn as ascii art.
+///
+/// ```
+/// # use advent_of_code::Answer;
+/// assert_eq!(Answer::from(42_usize), Answer::Integer(42));
+/// assert_eq!(Answer::from("abc"), Answer::Text("abc".into()));
+/// assert_eq!(Answer::from("#.\n.#"), Answer::Grid("#.\n.#".into()));
+/// ```
+///
+/// Integers are serialized as numbers and everything else as strings, so integers and text stay apart in JSON output.
+/// Integers that don't fit into 64 bits are serialized as strings, since most JSON parsers read them as floats.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    Grid(String),
+}
+
+impl Answer {
+    /// Returns `true` if the answer spans multiple lines.
+    pub fn is_grid(&self) -> bool {
+        matches!(self, Answer::Grid(_))
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(n) => write!(f, "{n}"),
+            Answer::Text(s) | Answer::Grid(s) => f.write_str(s),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+macro_rules! impl_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Integer(i128::from(n))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        // `usize` is at most 64 bits wide on all supported platforms.
+        Answer::Integer(n as i128)
+    }
+}
+
+impl From<isize> for Answer {
+    fn from(n: isize) -> Self {
+        Answer::Integer(n as i128)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        i128::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Integer)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        if s.contains('\n') {
+            Answer::Grid(s)
+        } else {
+            Answer::Text(s)
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::from(s.to_string())
+    }
+}
+
+impl From<Answer> for String {
+    fn from(answer: Answer) -> Self {
+        answer.to_string()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(n) => match (i64::try_from(*n), u64::try_from(*n)) {
+                (Ok(n), _) => serializer.serialize_i64(n),
+                (_, Ok(n)) => serializer.serialize_u64(n),
+                _ => serializer.collect_str(n),
+            },
+            Answer::Text(s) | Answer::Grid(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+struct AnswerVisitor;
+
+impl Visitor<'_> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an integer or a string")
+    }
+
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<Answer, E> {
+        Ok(Answer::from(n))
+    }
+
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<Answer, E> {
+        Ok(Answer::from(n))
+    }
+
+    fn visit_i128<E: de::Error>(self, n: i128) -> Result<Answer, E> {
+        Ok(Answer::from(n))
+    }
+
+    fn visit_u128<E: de::Error>(self, n: u128) -> Result<Answer, E> {
+        Ok(Answer::from(n))
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Answer, E> {
+        // integers outside of the 64 bit range are serialized as strings, see `Serialize`.
+        match s.parse::<i128>() {
+            Ok(n)
+                if i64::try_from(n).is_err() && u64::try_from(n).is_err() && n.to_string() == s =>
+            {
+                Ok(Answer::Integer(n))
+            }
+            _ => Ok(Answer::from(s)),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The return type of a solution part: an [`Option`] or a [`Result`] of anything that converts into an [`Answer`].
+///
+/// A part that returns [`None`] is not solved yet, a part that returns an error failed with its message.
+///
+/// ```
+/// # use advent_of_code::{Answer, IntoAnswer};
+/// assert_eq!(Some(42_u32).into_answer(), Ok(Answer::Integer(42)));
+/// assert_eq!(None::<u32>.into_answer(), Err(None));
+/// assert_eq!(Err::<u32, _>("no start").into_answer(), Err(Some("no start".into())));
+/// ```
+pub trait IntoAnswer {
+    /// Returns the answer, or the error message if there is one.
+    fn into_answer(self) -> Result<Answer, Option<String>>;
+}
+
+impl<T: Into<Answer>> IntoAnswer for Option<T> {
+    fn into_answer(self) -> Result<Answer, Option<String>> {
+        self.map(Into::into).ok_or(None)
+    }
+}
+
+impl<T: Into<Answer>, E: Display> IntoAnswer for Result<T, E> {
+    fn into_answer(self) -> Result<Answer, Option<String>> {
+        self.map(Into::into).map_err(|e| Some(e.to_string()))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answer;
+
+    #[test]
+    fn round_trips_through_json() {
+        for answer in [
+            Answer::from(42_u32),
+            Answer::from(-7_i64),
+            Answer::from(u64::MAX),
+            Answer::from(i128::MAX),
+            Answer::from(i128::MIN),
+            Answer::from("42"),
+            Answer::from("abc"),
+            Answer::from("#.\n.#"),
+        ] {
+            let json = serde_json::to_string(&answer).unwrap();
+            assert_eq!(
+                serde_json::from_str::<Answer>(&json).unwrap(),
+                answer,
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn serializes_integers_as_numbers() {
+        assert_eq!(serde_json::to_string(&Answer::from(42_u32)).unwrap(), "42");
+        assert_eq!(
+            serde_json::to_string(&Answer::from("42")).unwrap(),
+            r#""42""#
+        );
+        assert_eq!(
+            serde_json::to_string(&Answer::from(i128::MAX)).unwrap(),
+            format!(r#""{}""#, i128::MAX)
+        );
+    }
+}
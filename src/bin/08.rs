@@ -1,36 +1,18 @@
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, parse = Map::parse);
 
-use std::cmp::{max, min};
 use std::collections::HashMap;
 
+use advent_of_code::math;
 use winnow::ascii::{alphanumeric1, line_ending};
 use winnow::combinator::{delimited, iterator, opt, repeat, separated_pair, terminated};
 use winnow::stream::AsChar;
 use winnow::token::{any, take_till};
 use winnow::{PResult, Parser};
 
-fn gcd(a: usize, b: usize) -> usize {
-    match ((a, b), (a & 1, b & 1)) {
-        ((x, y), _) if x == y => y,
-        ((0, x), _) | ((x, 0), _) => x,
-        ((x, y), (0, 1)) | ((y, x), (1, 0)) => gcd(x >> 1, y),
-        ((x, y), (0, 0)) => gcd(x >> 1, y >> 1) << 1,
-        ((x, y), (1, 1)) => {
-            let (x, y) = (min(x, y), max(x, y));
-            gcd((y - x) >> 1, x)
-        }
-        _ => unreachable!(),
-    }
-}
-
-fn lcm(a: usize, b: usize) -> usize {
-    a * b / gcd(a, b)
-}
-
 type NodeMap<'a> = HashMap<&'a str, [&'a str; 2]>;
 type NodeElem<'a> = (&'a str, [&'a str; 2]);
 
-struct Map<'a> {
+pub struct Map<'a> {
     directions: Vec<usize>,
     graph: NodeMap<'a>,
 }
@@ -83,9 +65,7 @@ impl<'a> Map<'a> {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let map = Map::parse(input);
-
+pub fn part_one(map: &Map) -> Option<usize> {
     let mut steps = 0;
     let num_directions = map.directions.len();
 
@@ -99,8 +79,7 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(steps)
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let map = Map::parse(input);
+pub fn part_two(map: &Map) -> Option<usize> {
     let num_directions = map.directions.len();
 
     let starting_nodes = map
@@ -120,12 +99,7 @@ pub fn part_two(input: &str) -> Option<usize> {
         }
     }
 
-    let mut least_common_multiple = steps_taken[0];
-    for finish in steps_taken.iter().skip(1) {
-        least_common_multiple = lcm(least_common_multiple, *finish);
-    }
-
-    Some(least_common_multiple)
+    Some(math::lcm_all(steps_taken))
 }
 
 #[cfg(test)]
@@ -165,7 +139,8 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", DAY);
+        let result = part_one(&Map::parse(&input));
         assert_eq!(result, Some(2));
 
         let input2 = r#"LLR
@@ -173,7 +148,7 @@ mod tests {
 AAA = (BBB, BBB)
 BBB = (AAA, ZZZ)
 ZZZ = (ZZZ, ZZZ)"#;
-        assert_eq!(part_one(input2), Some(6))
+        assert_eq!(part_one(&Map::parse(input2)), Some(6))
     }
 
     #[test]
@@ -188,7 +163,7 @@ ZZZ = (ZZZ, ZZZ)"#;
 22C = (22Z, 22Z)
 22Z = (22B, 22B)
 XXX = (XXX, XXX)"#;
-        let result = part_two(input);
+        let result = part_two(&Map::parse(input));
         assert_eq!(result, Some(6));
     }
 }
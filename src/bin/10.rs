@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use advent_of_code::geom::{self, Point2};
 
 advent_of_code::solution!(10);
 
@@ -88,37 +88,6 @@ fn find_loop_pipes(start: Point, grid: &[Vec<char>]) -> Vec<Point> {
     pipes
 }
 
-fn have_connection((conn_1, conn_2): (Point, Point), to_find: Point) -> bool {
-    conn_1 == to_find || conn_2 == to_find
-}
-
-fn count_inner_tiles(
-    grid: &[Vec<char>],
-    pipes: HashSet<Point>,
-    start_connections: (Point, Point),
-) -> usize {
-    let mut inner_tiles = 0;
-
-    for y in 0..grid.len() {
-        let mut outside = true;
-
-        for x in 0..grid[0].len() {
-            if let Some(pos) = pipes.get(&(x, y)) {
-                // `pipe_connections` doesn't know what the shape of the starting tile is
-                // so we pass them connections manually now that we know
-                let connections = pipe_connections(*pos, grid).unwrap_or(start_connections);
-                if have_connection(connections, (x, y + 1)) {
-                    outside = !outside;
-                }
-            } else if !outside {
-                inner_tiles += 1;
-            }
-        }
-    }
-
-    inner_tiles
-}
-
 pub fn part_one(input: &str) -> Option<usize> {
     let grid = build_grid(input);
     let start = find_start(&grid);
@@ -131,11 +100,13 @@ pub fn part_two(input: &str) -> Option<usize> {
     let grid = build_grid(input);
     let start = find_start(&grid);
 
-    let pipes = find_loop_pipes(start, &grid);
-    let start_connections = (pipes[1], pipes[pipes.len() - 1]);
-    let pipes_set: HashSet<(usize, usize)> = pipes.into_iter().collect();
+    // the pipes of the loop are the vertices of a polygon, the inner tiles are the points inside of it.
+    let pipes = find_loop_pipes(start, &grid)
+        .into_iter()
+        .map(Point2::from)
+        .collect::<Vec<_>>();
 
-    Some(count_inner_tiles(&grid, pipes_set, start_connections))
+    Some(geom::interior_points(&pipes))
 }
 
 #[cfg(test)]
@@ -150,7 +121,7 @@ mod tests {
 
     #[test]
     fn test_part_one_two() {
-        let result = part_one(&advent_of_code::template::read_file_part(
+        let result = part_one(&advent_of_code::template::read_file_numbered(
             "examples", DAY, 2,
         ));
         assert_eq!(result, Some(8));
@@ -158,7 +129,7 @@ mod tests {
 
     #[test]
     fn test_part_two_one() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let result = part_two(&advent_of_code::template::read_file_numbered(
             "examples", DAY, 3,
         ));
         assert_eq!(result, Some(4));
@@ -166,7 +137,7 @@ mod tests {
 
     #[test]
     fn test_part_two_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let result = part_two(&advent_of_code::template::read_file_numbered(
             "examples", DAY, 4,
         ));
         assert_eq!(result, Some(10));
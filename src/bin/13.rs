@@ -1,19 +1,9 @@
-advent_of_code::solution!(13);
-
-fn transpose(grid: &[Vec<char>]) -> Vec<Vec<char>> {
-    let mut next_grid = vec![vec!['?'; grid.len()]; grid[0].len()];
+use advent_of_code::grid::Grid;
 
-    for (i, row) in grid.iter().enumerate() {
-        for (j, char) in row.iter().enumerate() {
-            next_grid[j][i] = *char;
-        }
-    }
-
-    next_grid
-}
+advent_of_code::solution!(13);
 
-fn find_reflection(grid: &[Vec<char>]) -> usize {
-    let rows = grid.len();
+fn find_reflection(grid: &Grid<u8>) -> usize {
+    let rows = grid.rows();
 
     for i in 1..rows {
         let mut is_reflection = true;
@@ -22,7 +12,7 @@ fn find_reflection(grid: &[Vec<char>]) -> usize {
             if i + j > rows {
                 break;
             }
-            if grid[i - j] != grid[i + j - 1] {
+            if grid.row(i - j) != grid.row(i + j - 1) {
                 is_reflection = false;
                 break;
             }
@@ -35,8 +25,8 @@ fn find_reflection(grid: &[Vec<char>]) -> usize {
     0
 }
 
-fn find_reflection_two(grid: &[Vec<char>]) -> usize {
-    let rows = grid.len();
+fn find_reflection_two(grid: &Grid<u8>) -> usize {
+    let rows = grid.rows();
 
     for i in 1..rows {
         let mut found_difference = false;
@@ -46,8 +36,8 @@ fn find_reflection_two(grid: &[Vec<char>]) -> usize {
             if i + j > rows {
                 break;
             }
-            let a = &grid[i - j];
-            let b = &grid[i + j - 1];
+            let a = grid.row(i - j);
+            let b = grid.row(i + j - 1);
             if a != b {
                 if !found_difference && has_only_one_difference(a, b) {
                     found_difference = true;
@@ -65,7 +55,7 @@ fn find_reflection_two(grid: &[Vec<char>]) -> usize {
     0
 }
 
-fn has_only_one_difference(a: &[char], b: &[char]) -> bool {
+fn has_only_one_difference(a: &[u8], b: &[u8]) -> bool {
     let mut differences = 0;
     for i in 0..a.len() {
         if a[i] != b[i] {
@@ -84,12 +74,9 @@ pub fn part_one(input: &str) -> Option<usize> {
         .trim()
         .split("\n\n")
         .map(|s| {
-            let grid = s
-                .lines()
-                .map(|l| l.chars().collect::<Vec<_>>())
-                .collect::<Vec<_>>();
+            let grid: Grid<u8> = s.parse().unwrap();
 
-            let transposed_grid = transpose(&grid);
+            let transposed_grid = grid.transpose();
             let col_reflection = find_reflection(&transposed_grid);
 
             if col_reflection == 0 {
@@ -108,12 +95,9 @@ pub fn part_two(input: &str) -> Option<usize> {
         .trim()
         .split("\n\n")
         .map(|s| {
-            let grid = s
-                .lines()
-                .map(|l| l.chars().collect::<Vec<_>>())
-                .collect::<Vec<_>>();
+            let grid: Grid<u8> = s.parse().unwrap();
 
-            let transposed_grid = transpose(&grid);
+            let transposed_grid = grid.transpose();
             let col_reflection = find_reflection_two(&transposed_grid);
 
             if col_reflection == 0 {
@@ -139,7 +123,7 @@ mod tests {
 
     #[test]
     fn test_part_one_from_real_input() {
-        let result = part_one(&advent_of_code::template::read_file_part(
+        let result = part_one(&advent_of_code::template::read_file_numbered(
             "examples", DAY, 2,
         ));
         assert_eq!(result, Some(200))
@@ -1,21 +1,24 @@
 use std::collections::HashMap;
 
+use advent_of_code::geom::Point2;
+use advent_of_code::grid::Grid;
+
 advent_of_code::solution!(14);
 
-fn move_rocks_north(grid: &mut [Vec<char>]) {
-    for col in 0..grid[0].len() {
+fn move_rocks_north(grid: &mut Grid<u8>) {
+    for col in 0..grid.cols() {
         let mut available = 0;
 
-        for row in 0..grid.len() {
-            match grid[row][col] {
-                'O' => {
+        for row in 0..grid.rows() {
+            match grid[Point2::new(col, row)] {
+                b'O' => {
                     if available < row {
-                        grid[available][col] = 'O';
-                        grid[row][col] = '.';
+                        grid[Point2::new(col, available)] = b'O';
+                        grid[Point2::new(col, row)] = b'.';
                     }
                     available += 1;
                 }
-                '#' => {
+                b'#' => {
                     available = row + 1;
                 }
                 _ => {}
@@ -24,20 +27,20 @@ fn move_rocks_north(grid: &mut [Vec<char>]) {
     }
 }
 
-fn move_rocks_west(grid: &mut [Vec<char>]) {
-    for row in 0..grid.len() {
+fn move_rocks_west(grid: &mut Grid<u8>) {
+    for row in 0..grid.rows() {
         let mut available = 0;
 
-        for col in 0..grid[0].len() {
-            match grid[row][col] {
-                'O' => {
+        for col in 0..grid.cols() {
+            match grid[Point2::new(col, row)] {
+                b'O' => {
                     if available < col {
-                        grid[row][available] = 'O';
-                        grid[row][col] = '.';
+                        grid[Point2::new(available, row)] = b'O';
+                        grid[Point2::new(col, row)] = b'.';
                     }
                     available += 1;
                 }
-                '#' => {
+                b'#' => {
                     available = col + 1;
                 }
                 _ => {}
@@ -46,20 +49,20 @@ fn move_rocks_west(grid: &mut [Vec<char>]) {
     }
 }
 
-fn move_rocks_south(grid: &mut [Vec<char>]) {
-    for col in 0..grid[0].len() {
-        let mut available = grid.len() as isize - 1;
+fn move_rocks_south(grid: &mut Grid<u8>) {
+    for col in 0..grid.cols() {
+        let mut available = grid.rows() as isize - 1;
 
-        for row in (0..grid.len()).rev() {
-            match grid[row][col] {
-                'O' => {
+        for row in (0..grid.rows()).rev() {
+            match grid[Point2::new(col, row)] {
+                b'O' => {
                     if available > row as isize {
-                        grid[available as usize][col] = 'O';
-                        grid[row][col] = '.';
+                        grid[Point2::new(col, available as usize)] = b'O';
+                        grid[Point2::new(col, row)] = b'.';
                     }
                     available -= 1;
                 }
-                '#' => {
+                b'#' => {
                     available = row as isize - 1;
                 }
                 _ => {}
@@ -68,20 +71,20 @@ fn move_rocks_south(grid: &mut [Vec<char>]) {
     }
 }
 
-fn move_rocks_east(grid: &mut [Vec<char>]) {
-    for row in 0..grid.len() {
-        let mut available = grid[0].len() as isize - 1;
+fn move_rocks_east(grid: &mut Grid<u8>) {
+    for row in 0..grid.rows() {
+        let mut available = grid.cols() as isize - 1;
 
-        for col in (0..grid[0].len()).rev() {
-            match grid[row][col] {
-                'O' => {
+        for col in (0..grid.cols()).rev() {
+            match grid[Point2::new(col, row)] {
+                b'O' => {
                     if available > col as isize {
-                        grid[row][available as usize] = 'O';
-                        grid[row][col] = '.';
+                        grid[Point2::new(available as usize, row)] = b'O';
+                        grid[Point2::new(col, row)] = b'.';
                     }
                     available -= 1;
                 }
-                '#' => {
+                b'#' => {
                     available = col as isize - 1;
                 }
                 _ => {}
@@ -90,37 +93,15 @@ fn move_rocks_east(grid: &mut [Vec<char>]) {
     }
 }
 
-fn boulder_load(grid: &[Vec<char>]) -> usize {
-    grid.iter()
+fn boulder_load(grid: &Grid<u8>) -> usize {
+    grid.iter_rows()
         .enumerate()
-        .map(|(i, row)| row.iter().filter(|&&c| c == 'O').count() * (grid.len() - i))
+        .map(|(i, row)| row.iter().filter(|&&c| c == b'O').count() * (grid.rows() - i))
         .sum()
 }
 
-fn _print_grid(grid: &[Vec<char>]) {
-    for row in grid.iter() {
-        for col in row.iter() {
-            print!("{col}");
-        }
-        println!()
-    }
-    println!()
-}
-
-fn stringify_grid(grid: &[Vec<char>]) -> String {
-    let mut str = String::with_capacity(grid[0].len() * grid.len());
-    for row in grid.iter() {
-        str.push_str(&row.iter().collect::<String>());
-    }
-    str
-}
-
 pub fn part_one(input: &str) -> Option<usize> {
-    let mut grid = input
-        .trim()
-        .lines()
-        .map(|l| l.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+    let mut grid: Grid<u8> = input.trim().parse().unwrap();
 
     move_rocks_north(&mut grid);
     let load = boulder_load(&grid);
@@ -129,11 +110,7 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let mut grid = input
-        .trim()
-        .lines()
-        .map(|l| l.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
+    let mut grid: Grid<u8> = input.trim().parse().unwrap();
 
     let mut map = HashMap::new();
 
@@ -146,13 +123,11 @@ pub fn part_two(input: &str) -> Option<usize> {
         move_rocks_south(&mut grid);
         move_rocks_east(&mut grid);
 
-        let grid_as_str = stringify_grid(&grid);
-
-        if let Some(seen) = map.get(&grid_as_str) {
+        if let Some(seen) = map.get(&grid) {
             i = cycles - (cycles - i) % (i - seen);
         }
 
-        map.insert(grid_as_str, i);
+        map.insert(grid.clone(), i);
         i += 1;
     }
 
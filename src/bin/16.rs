@@ -1,117 +1,61 @@
-use std::collections::VecDeque;
-
+use advent_of_code::geom::{Direction, Point2};
+use advent_of_code::grid::Grid;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use rustc_hash::FxHashSet;
 
 advent_of_code::solution!(16);
 
-#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn next(&self, current: Position) -> Position {
-        match self {
-            Direction::Up => (current.0 - 1, current.1),
-            Direction::Down => (current.0 + 1, current.1),
-            Direction::Left => (current.0, current.1 - 1),
-            Direction::Right => (current.0, current.1 + 1),
-        }
-    }
-}
-
-type Position = (isize, isize);
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Beam {
-    position: Position,
+    position: Point2<usize>,
     direction: Direction,
 }
 
 impl Beam {
-    fn new(position: Position, direction: Direction) -> Self {
+    fn new(position: Point2<usize>, direction: Direction) -> Self {
         Self {
             position,
             direction,
         }
     }
 
-    fn next(&mut self, grid: &[&[u8]]) -> Option<(Beam, Beam)> {
-        let (y, x) = self.position;
-
-        self.direction = match grid[y as usize][x as usize] {
-            b'.' => self.direction,
-            b'/' => match self.direction {
-                Direction::Down => Direction::Left,
-                Direction::Right => Direction::Up,
-                Direction::Up => Direction::Right,
-                Direction::Left => Direction::Down,
-            },
-            b'\\' => match self.direction {
-                Direction::Down => Direction::Right,
-                Direction::Right => Direction::Down,
-                Direction::Up => Direction::Left,
-                Direction::Left => Direction::Up,
-            },
-            b'-' => match self.direction {
-                Direction::Left | Direction::Right => self.direction,
-                Direction::Up | Direction::Down => {
-                    return Some((
-                        Beam::new(self.position, Direction::Left),
-                        Beam::new(self.position, Direction::Right),
-                    ))
-                }
-            },
-            b'|' => match self.direction {
-                Direction::Up | Direction::Down => self.direction,
-                Direction::Left | Direction::Right => {
-                    return Some((
-                        Beam::new(self.position, Direction::Up),
-                        Beam::new(self.position, Direction::Down),
-                    ))
-                }
-            },
+    /// The directions the beam continues in after passing a tile, a splitter returns two.
+    fn directions(&self, tile: u8) -> (Direction, Option<Direction>) {
+        let direction = self.direction;
+
+        match tile {
+            b'.' => (direction, None),
+            b'/' if direction.is_vertical() => (direction.turn_right(), None),
+            b'/' => (direction.turn_left(), None),
+            b'\\' if direction.is_vertical() => (direction.turn_left(), None),
+            b'\\' => (direction.turn_right(), None),
+            b'-' if direction.is_horizontal() => (direction, None),
+            b'-' => (Direction::Left, Some(Direction::Right)),
+            b'|' if direction.is_vertical() => (direction, None),
+            b'|' => (Direction::Up, Some(Direction::Down)),
             _ => unreachable!(),
-        };
-
-        self.position = self.direction.next(self.position);
-        None
+        }
     }
 }
 
-fn get_energized_cell_count(beam: Beam, grid: &[&[u8]]) -> usize {
-    let mut beams: VecDeque<Beam> = VecDeque::new();
-    beams.push_back(beam);
+fn get_energized_cell_count(beam: Beam, grid: &Grid<u8>) -> usize {
+    let bounds = grid.bounds();
+    let mut beams = vec![beam];
 
-    let mut energized_cells: FxHashSet<Position> = FxHashSet::default();
+    let mut energized_cells: FxHashSet<Point2<usize>> = FxHashSet::default();
     let mut seen: FxHashSet<Beam> = FxHashSet::default();
 
-    while let Some(mut beam) = beams.pop_front() {
-        loop {
-            let current_pos = beam.position;
-
-            if seen.contains(&beam) {
-                break;
-            }
+    while let Some(beam) = beams.pop() {
+        if !seen.insert(beam) {
+            continue;
+        }
+        energized_cells.insert(beam.position);
 
-            if current_pos.0 < 0
-                || current_pos.0 >= grid.len() as isize
-                || current_pos.1 < 0
-                || current_pos.1 >= grid[0].len() as isize
-            {
-                break;
-            }
-            energized_cells.insert(current_pos);
-            seen.insert(beam);
+        let (direction, split) = beam.directions(grid[beam.position]);
 
-            if let Some((beam1, beam2)) = beam.next(grid) {
-                beams.push_back(beam1);
-                beams.push_back(beam2);
-                break;
+        for direction in std::iter::once(direction).chain(split) {
+            if let Some(position) = beam.position.step_within(direction, &bounds) {
+                beams.push(Beam::new(position, direction));
             }
         }
     }
@@ -120,38 +64,30 @@ fn get_energized_cell_count(beam: Beam, grid: &[&[u8]]) -> usize {
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let grid = input
-        .trim()
-        .lines()
-        .map(|l| l.as_bytes())
-        .collect::<Vec<_>>();
+    let grid: Grid<u8> = input.trim().parse().unwrap();
 
     Some(get_energized_cell_count(
-        Beam::new((0, 0), Direction::Right),
+        Beam::new(Point2::ORIGIN, Direction::Right),
         &grid,
     ))
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let grid = input
-        .trim()
-        .lines()
-        .map(|l| l.as_bytes())
-        .collect::<Vec<_>>();
+    let grid: Grid<u8> = input.trim().parse().unwrap();
 
-    let rows = grid.len();
-    let cols = grid[0].len();
+    let rows = grid.rows();
+    let cols = grid.cols();
 
     let mut starts = Vec::with_capacity((rows * 2) + (cols * 2));
 
     for y in 0..rows {
-        starts.push(Beam::new((y as isize, 0), Direction::Right));
-        starts.push(Beam::new((y as isize, cols as isize - 1), Direction::Left));
+        starts.push(Beam::new(Point2::new(0, y), Direction::Right));
+        starts.push(Beam::new(Point2::new(cols - 1, y), Direction::Left));
     }
 
     for x in 0..cols {
-        starts.push(Beam::new((0, x as isize), Direction::Down));
-        starts.push(Beam::new((rows as isize - 1, x as isize), Direction::Up));
+        starts.push(Beam::new(Point2::new(x, 0), Direction::Down));
+        starts.push(Beam::new(Point2::new(x, rows - 1), Direction::Up));
     }
 
     starts
@@ -1,99 +1,54 @@
-use std::collections::BinaryHeap;
-
-use rustc_hash::FxHashMap;
+use advent_of_code::geom::{Direction, Point2};
+use advent_of_code::grid::Grid;
+use advent_of_code::search;
 
 advent_of_code::solution!(17);
 
-const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct State {
-    cost: usize,
-    position: (usize, usize),
-    direction: (isize, isize),
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Crucible {
+    position: Point2<usize>,
+    direction: Direction,
     steps_in_same_direction: usize,
 }
 
-impl Ord for State {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
+fn shortest_path(grid: &Grid<u8>, min_steps: usize, max_steps: usize) -> Option<usize> {
+    let bounds = grid.bounds();
+    let end = Point2::new(grid.cols() - 1, grid.rows() - 1);
 
-impl PartialOrd for State {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-fn shortest_path(grid: &[&[u8]], min_steps: usize, max_steps: usize) -> Option<usize> {
-    let rows = grid.len();
-    let cols = grid[0].len();
-    let mut distances = FxHashMap::default();
+    let start = Crucible {
+        position: Point2::ORIGIN,
+        direction: Direction::Right,
+        steps_in_same_direction: 1,
+    };
 
-    let start = (0, 0);
-    let end = (rows - 1, cols - 1);
+    let successors = |crucible: &Crucible| {
+        let crucible = *crucible;
+        let bounds = &bounds;
 
-    let mut heap = BinaryHeap::new();
+        Direction::ALL.into_iter().filter_map(move |direction| {
+            // Can't go backwards
+            if direction == crucible.direction.reverse() {
+                return None;
+            }
 
-    heap.push(State {
-        cost: 0,
-        position: start,
-        direction: DIRECTIONS[3],
-        steps_in_same_direction: 1,
-    });
-
-    while let Some(state) = heap.pop() {
-        let State {
-            cost,
-            position,
-            direction,
-            steps_in_same_direction,
-        } = state;
-
-        if position == end {
-            return Some(cost);
-        }
-
-        if cost
-            > *distances
-                .get(&(position, direction, steps_in_same_direction))
-                .unwrap_or(&usize::MAX)
-        {
-            continue;
-        }
-
-        'directions: for next_direction in DIRECTIONS {
-            let mut steps = if direction == next_direction {
-                steps_in_same_direction + 1
+            let mut steps = if direction == crucible.direction {
+                crucible.steps_in_same_direction + 1
             } else {
                 1
             };
 
-            // Can't go in the same direction more than 3 times
+            // Can't go in the same direction more than `max_steps` times
             if steps > max_steps {
-                continue;
+                return None;
             }
 
-            // Can't go backwards
-            if direction == (-next_direction.0, -next_direction.1) {
-                continue;
-            }
-
-            let mut next_row = position.0;
-            let mut next_col = position.1;
-            let mut next_cost = cost;
+            let mut position = crucible.position;
+            let mut cost = 0;
 
             loop {
-                next_row = next_row.wrapping_add_signed(next_direction.0);
-                next_col = next_col.wrapping_add_signed(next_direction.1);
-
                 // Don't exit the grid
-                if next_row >= rows || next_col >= cols {
-                    continue 'directions;
-                }
-
-                next_cost += (grid[next_row][next_col] - b'0') as usize;
+                position = position.step_within(direction, bounds)?;
+                cost += usize::from(grid[position] - b'0');
 
                 if steps >= min_steps {
                     break;
@@ -102,39 +57,33 @@ fn shortest_path(grid: &[&[u8]], min_steps: usize, max_steps: usize) -> Option<u
                 steps += 1;
             }
 
-            let key = ((next_row, next_col), next_direction, steps);
-            if next_cost < *distances.get(&key).unwrap_or(&usize::MAX) {
-                heap.push(State {
-                    cost: next_cost,
-                    position: (next_row, next_col),
-                    direction: next_direction,
-                    steps_in_same_direction: steps,
-                });
-
-                distances.insert(key, next_cost);
-            }
-        }
-    }
+            let next = Crucible {
+                position,
+                direction,
+                steps_in_same_direction: steps,
+            };
 
-    None
+            Some((next, cost))
+        })
+    };
+
+    search::astar(
+        start,
+        successors,
+        |crucible| crucible.position.manhattan(end),
+        |crucible| crucible.position == end,
+    )
+    .map(|found| found.cost)
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let grid = input
-        .trim()
-        .lines()
-        .map(|l| l.as_bytes())
-        .collect::<Vec<&[u8]>>();
+    let grid: Grid<u8> = input.trim().parse().unwrap();
 
     shortest_path(&grid, 1, 3)
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let grid = input
-        .trim()
-        .lines()
-        .map(|l| l.as_bytes())
-        .collect::<Vec<&[u8]>>();
+    let grid: Grid<u8> = input.trim().parse().unwrap();
 
     shortest_path(&grid, 4, 10)
 }
@@ -158,7 +107,9 @@ mod tests {
     #[test]
     fn test_part_two_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples",
+            DAY,
+            advent_of_code::part!(2),
         ));
         assert_eq!(result, Some(71));
     }
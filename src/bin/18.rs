@@ -1,15 +1,23 @@
+use advent_of_code::geom::{self, Direction, Point2};
+
 advent_of_code::solution!(18);
 
-const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (0, -1), (-1, 0)];
+/// The directions of the last hex digit of a color.
+const COLOR_DIRECTIONS: [Direction; 4] = [
+    Direction::Right,
+    Direction::Down,
+    Direction::Left,
+    Direction::Up,
+];
 
 struct Instruction {
-    direction: usize,
-    length: usize,
-    direction_2: usize,
-    length_2: usize,
+    direction: Direction,
+    length: isize,
+    direction_2: Direction,
+    length_2: isize,
 }
 
-type Point = (isize, isize);
+type Point = Point2<isize>;
 
 fn parse_line(line: &str) -> Instruction {
     let [direction, length, color]: [&str; 3] = line
@@ -18,21 +26,19 @@ fn parse_line(line: &str) -> Instruction {
         .try_into()
         .unwrap();
 
-    let direction = match direction {
-        "U" => 3,
-        "D" => 1,
-        "L" => 2,
-        "R" => 0,
-        _ => unreachable!(),
-    };
+    let direction = direction
+        .chars()
+        .next()
+        .and_then(Direction::from_char)
+        .unwrap();
 
     let length = length.parse().unwrap();
 
     let color = color.strip_prefix("(#").unwrap().strip_suffix(')').unwrap();
 
     let (length_2, direction_2) = color.split_at(5);
-    let direction_2 = usize::from_str_radix(direction_2, 16).unwrap();
-    let length_2 = usize::from_str_radix(length_2, 16).unwrap();
+    let direction_2 = COLOR_DIRECTIONS[usize::from_str_radix(direction_2, 16).unwrap()];
+    let length_2 = isize::from_str_radix(length_2, 16).unwrap();
 
     Instruction {
         direction,
@@ -43,16 +49,15 @@ fn parse_line(line: &str) -> Instruction {
 }
 
 fn find_points(input: &str) -> Vec<Point> {
-    let mut current_position: Point = (0, 0);
+    let mut current_position = Point::ORIGIN;
     let mut points = vec![current_position];
 
     let instructions = input.trim().lines().map(parse_line).collect::<Vec<_>>();
 
     for instruction in instructions {
-        let direction = DIRECTIONS[instruction.direction];
-        let next_y = current_position.0 + (direction.0 * instruction.length as isize);
-        let next_x = current_position.1 + (direction.1 * instruction.length as isize);
-        current_position = (next_y, next_x);
+        current_position = current_position
+            .step_by(instruction.direction, instruction.length)
+            .unwrap();
         points.push(current_position);
     }
 
@@ -60,60 +65,29 @@ fn find_points(input: &str) -> Vec<Point> {
 }
 
 fn find_points_2(input: &str) -> Vec<Point> {
-    let mut current_position: Point = (0, 0);
+    let mut current_position = Point::ORIGIN;
     let mut points = vec![current_position];
 
     let instructions = input.trim().lines().map(parse_line).collect::<Vec<_>>();
 
     for instruction in instructions {
-        let direction = DIRECTIONS[instruction.direction_2];
-        let next_y = current_position.0 + (direction.0 * instruction.length_2 as isize);
-        let next_x = current_position.1 + (direction.1 * instruction.length_2 as isize);
-        current_position = (next_y, next_x);
+        current_position = current_position
+            .step_by(instruction.direction_2, instruction.length_2)
+            .unwrap();
         points.push(current_position);
     }
 
     points
 }
 
-fn polygon_area(points: &[Point]) -> isize {
-    let n = points.len();
-    let mut area = 0;
-
-    for i in 0..n {
-        let j = (i + 1) % n;
-        area += (points[i].1 * points[j].0) - (points[i].0 * points[j].1);
-    }
-
-    area / 2
-}
-
-fn polygon_perimiter(points: &[Point]) -> isize {
-    let n = points.len();
-
-    let mut perimeter = 0;
-    for i in 0..n {
-        let j = (i + 1) % n;
-        perimeter += (points[i].0 - points[j].0).abs() + (points[i].1 - points[j].1).abs();
-    }
-
-    perimeter
-}
-
 pub fn part_one(input: &str) -> Option<isize> {
     let points = find_points(input);
-    let area = polygon_area(&points);
-    let perimeter = polygon_perimiter(&points);
-
-    Some(area + (perimeter / 2) + 1)
+    Some(geom::interior_points(&points) + geom::boundary_points(&points))
 }
 
 pub fn part_two(input: &str) -> Option<isize> {
     let points = find_points_2(input);
-    let area = polygon_area(&points);
-    let perimeter = polygon_perimiter(&points);
-
-    Some(area + (perimeter / 2) + 1)
+    Some(geom::interior_points(&points) + geom::boundary_points(&points))
 }
 
 #[cfg(test)]
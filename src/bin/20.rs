@@ -1,5 +1,7 @@
 use std::collections::{HashMap, VecDeque};
 
+use advent_of_code::math;
+
 advent_of_code::solution!(20);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -104,7 +106,7 @@ pub fn part_one(input: &str) -> Option<usize> {
     let conjunction_nodes = nodes
         .iter()
         .filter(|(_key, node)| matches!(node.kind, NodeKind::Conjunction(_)))
-        .map(|(&key, _)| key.clone())
+        .map(|(&key, _)| key)
         .collect::<Vec<_>>();
 
     for conjunction_node in conjunction_nodes.iter() {
@@ -157,7 +159,7 @@ pub fn part_two(input: &str) -> Option<usize> {
     let conjunction_nodes = nodes
         .iter()
         .filter(|(_key, node)| matches!(node.kind, NodeKind::Conjunction(_)))
-        .map(|(&key, _)| key.clone())
+        .map(|(&key, _)| key)
         .collect::<Vec<_>>();
 
     let rx_sender = nodes
@@ -201,13 +203,14 @@ pub fn part_two(input: &str) -> Option<usize> {
 
             if node.kind == NodeKind::Conjunction(true)
                 && rx_inputs.contains(&node.name)
-                && trigger_presses.get(node.name).is_none()
+                && !trigger_presses.contains_key(node.name)
             {
                 trigger_presses.insert(node.name, i);
             }
 
             if trigger_presses.len() == rx_inputs.len() {
-                return Some(trigger_presses.values().product::<usize>());
+                // the inputs trigger in cycles, `rx` gets a low pulse once all of them trigger in the same press.
+                return Some(math::lcm_all(trigger_presses.values().copied()));
             }
         }
     }
@@ -227,7 +230,7 @@ mod tests {
 
     #[test]
     fn test_part_one_two() {
-        let result = part_one(&advent_of_code::template::read_file_part(
+        let result = part_one(&advent_of_code::template::read_file_numbered(
             "examples", DAY, 2,
         ));
         assert_eq!(result, Some(11687500));
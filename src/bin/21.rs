@@ -1,32 +1,10 @@
-use std::collections::{HashMap, VecDeque};
+use advent_of_code::geom::Point2;
+use advent_of_code::grid::Grid;
+use advent_of_code::search;
+use rustc_hash::FxHashMap;
 
 advent_of_code::solution!(21);
 
-fn find_start(grid: &[Vec<char>]) -> (usize, usize) {
-    for (i, row) in grid.iter().enumerate() {
-        for (j, &col) in row.iter().enumerate() {
-            if col == 'S' {
-                return (i, j);
-            }
-        }
-    }
-
-    unreachable!()
-}
-
-fn neighbors((row, col): (usize, usize), grid: &[Vec<char>]) -> Vec<(usize, usize)> {
-    vec![
-        (row.wrapping_sub(1), col),
-        (row + 1, col),
-        (row, col.wrapping_sub(1)),
-        (row, col + 1),
-    ]
-    .into_iter()
-    .filter(|(i, j)| *i < grid.len() && *j < grid[0].len())
-    .filter(|(i, j)| grid[*i][*j] != '#')
-    .collect()
-}
-
 fn solve(input: &str, steps_to_take: usize) -> Option<usize> {
     let visited = bfs(input);
     let after_steps = visited
@@ -37,33 +15,13 @@ fn solve(input: &str, steps_to_take: usize) -> Option<usize> {
     Some(after_steps)
 }
 
-fn bfs(input: &str) -> HashMap<(usize, usize), usize> {
-    let grid = input
-        .trim()
-        .lines()
-        .map(|l| l.chars().collect::<Vec<_>>())
-        .collect::<Vec<_>>();
-
-    let start = find_start(&grid);
-
-    let mut visited: HashMap<(usize, usize), usize> = HashMap::new();
-    let mut queue: VecDeque<(usize, (usize, usize))> = VecDeque::new();
-    queue.push_back((0, start));
-
-    while let Some((steps, position)) = queue.pop_front() {
-        if visited.contains_key(&position) {
-            continue;
-        }
-        visited.insert(position, steps);
-
-        for neighbor in neighbors(position, &grid) {
-            if !visited.contains_key(&neighbor) {
-                queue.push_back((steps + 1, neighbor));
-            }
-        }
-    }
+fn bfs(input: &str) -> FxHashMap<Point2<usize>, usize> {
+    let grid: Grid<u8> = input.trim().parse().unwrap();
+    let start = grid.find(&b'S').unwrap();
 
-    visited
+    search::distances(start, |&position| {
+        grid.neighbors4(position).filter(|&p| grid[p] != b'#')
+    })
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -1,10 +1,12 @@
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use advent_of_code::search;
+
 advent_of_code::solution!(25);
 
 type Graph<'a> = HashMap<&'a str, HashSet<&'a str>>;
 
-fn parse(input: &str) -> Graph {
+fn parse(input: &str) -> Graph<'_> {
     let mut graph: Graph = HashMap::new();
 
     for line in input.lines() {
@@ -57,23 +59,7 @@ fn find_most_common_connection<'a>(graph: &Graph<'a>) -> [&'a str; 2] {
 fn calculate_graph_size(graph: &Graph<'_>) -> usize {
     let start = graph.keys().next().unwrap();
 
-    let mut visited = HashSet::new();
-    let mut queue = VecDeque::from([start]);
-
-    while let Some(vertex) = queue.pop_front() {
-        if visited.contains(&vertex) {
-            continue;
-        }
-        visited.insert(vertex);
-
-        for connection in graph[vertex].iter() {
-            if !visited.contains(&connection) {
-                queue.push_back(connection);
-            }
-        }
-    }
-
-    visited.len()
+    search::distances(*start, |vertex| graph[vertex].iter().copied()).len()
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
@@ -89,7 +75,7 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(size * (graph.len() - size))
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
+pub fn part_two(_input: &str) -> Option<usize> {
     None
 }
 
@@ -0,0 +1,568 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::math::{self, Coordinate, Integer};
+
+/// One of the four directions on a grid.
+///
+/// Directions follow screen coordinates: `y` grows downwards, so [`Direction::Up`] decreases `y`.
+///
+/// ```
+/// # use advent_of_code::geom::Direction;
+/// assert_eq!(Direction::Up.turn_right(), Direction::Right);
+/// assert_eq!(Direction::Up.turn_left(), Direction::Left);
+/// assert_eq!(Direction::Up.reverse(), Direction::Down);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Every direction, clockwise from [`Direction::Up`].
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// Parses the letter (`U`, `R`, `D`, `L`) or arrow (`^`, `>`, `v`, `<`) of a direction.
+    pub fn from_char(c: char) -> Option<Self> {
+        match c {
+            'U' | '^' => Some(Direction::Up),
+            'R' | '>' => Some(Direction::Right),
+            'D' | 'v' => Some(Direction::Down),
+            'L' | '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        self.turn_right().reverse()
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        self.turn_right().turn_right()
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+
+    /// The change of a point's coordinates when it steps in this direction, as `(x, y)`.
+    pub fn offset(self) -> Point2<isize> {
+        match self {
+            Direction::Up => Point2::new(0, -1),
+            Direction::Right => Point2::new(1, 0),
+            Direction::Down => Point2::new(0, 1),
+            Direction::Left => Point2::new(-1, 0),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A point on a plane, or a vector between two points.
+///
+/// ```
+/// # use advent_of_code::geom::{Direction, Point2};
+/// let point = Point2::new(3_usize, 0);
+/// assert_eq!(point.step(Direction::Right), Some(Point2::new(4, 0)));
+/// assert_eq!(point.step(Direction::Up), None);
+/// assert_eq!(point.manhattan(Point2::new(1, 2)), 4);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T: Coordinate> Point2<T> {
+    pub const ORIGIN: Self = Self::new(T::ZERO, T::ZERO);
+
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+
+    /// The [Manhattan distance](https://en.wikipedia.org/wiki/Taxicab_geometry) to another point.
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    /// Returns the adjacent point in a direction, or [`None`] if a coordinate would overflow.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        self.step_by(direction, T::ONE)
+    }
+
+    /// Returns the point `distance` away in a direction, or [`None`] if a coordinate would overflow.
+    pub fn step_by(self, direction: Direction, distance: T) -> Option<Self> {
+        let Self { x, y } = self;
+
+        Some(match direction {
+            Direction::Up => Self::new(x, y.checked_sub(distance)?),
+            Direction::Right => Self::new(x.checked_add(distance)?, y),
+            Direction::Down => Self::new(x, y.checked_add(distance)?),
+            Direction::Left => Self::new(x.checked_sub(distance)?, y),
+        })
+    }
+
+    /// Returns the adjacent point in a direction, if it is inside of `bounds`.
+    pub fn step_within(self, direction: Direction, bounds: &Bounds<T>) -> Option<Self> {
+        self.step(direction).filter(|p| bounds.contains(*p))
+    }
+
+    /// Returns the up to 4 orthogonally adjacent points, clockwise from the one above.
+    /// Points with coordinates that would overflow are skipped.
+    pub fn neighbors4(self) -> impl Iterator<Item = Self> {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(direction))
+    }
+}
+
+impl<T: Coordinate + Neg<Output = T>> Point2<T> {
+    /// Rotates the vector by 90 degrees to the right, in screen coordinates.
+    #[must_use]
+    pub fn rotate_right(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    /// Rotates the vector by 90 degrees to the left, in screen coordinates.
+    #[must_use]
+    pub fn rotate_left(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+}
+
+impl<T: Coordinate> Add for Point2<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Coordinate> AddAssign for Point2<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coordinate> Sub for Point2<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Coordinate> SubAssign for Point2<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coordinate> Mul<T> for Point2<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Coordinate + Neg<Output = T>> Neg for Point2<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+impl<T: Display> Display for Point2<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangle of points, from `min` (inclusive) to `max` (exclusive).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Bounds<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T: Coordinate> Bounds<T> {
+    pub fn new(min: Point2<T>, max: Point2<T>) -> Self {
+        Self { min, max }
+    }
+
+    /// The bounds of a `width` by `height` rectangle that starts at the origin.
+    pub fn from_size(width: T, height: T) -> Self {
+        Self::new(Point2::ORIGIN, Point2::new(width, height))
+    }
+
+    pub fn contains(&self, point: Point2<T>) -> bool {
+        (self.min.x..self.max.x).contains(&point.x) && (self.min.y..self.max.y).contains(&point.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A point in space, or a vector between two points.
+///
+/// ```
+/// # use advent_of_code::geom::Point3;
+/// let a = Point3::new(1, 2, 3);
+/// assert_eq!(a + Point3::new(1, 1, 1), Point3::new(2, 3, 4));
+/// assert_eq!(a.manhattan(Point3::new(-1, 2, 0)), 5);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: Coordinate> Point3<T> {
+    pub const ORIGIN: Self = Self::new(T::ZERO, T::ZERO, T::ZERO);
+
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+
+    /// The [Manhattan distance](https://en.wikipedia.org/wiki/Taxicab_geometry) to another point.
+    pub fn manhattan(self, other: Self) -> T {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+}
+
+impl<T: Coordinate> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl<T: Coordinate> AddAssign for Point3<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coordinate> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+impl<T: Coordinate> SubAssign for Point3<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coordinate> Mul<T> for Point3<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs, self.z * rhs)
+    }
+}
+
+impl<T: Coordinate + Neg<Output = T>> Neg for Point3<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> Self {
+        (point.x, point.y, point.z)
+    }
+}
+
+impl<T: Display> Display for Point3<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Twice the area of a polygon, which is always a whole number for points on a lattice.
+fn double_area<T: Integer>(vertices: &[Point2<T>]) -> T {
+    // the positive and negative terms of the shoelace formula are summed separately, so unsigned types don't underflow.
+    let (positive, negative) = edges(vertices).fold((T::ZERO, T::ZERO), |(pos, neg), (a, b)| {
+        (pos + a.x * b.y, neg + b.x * a.y)
+    });
+
+    positive.distance(negative)
+}
+
+/// The edges of a polygon, including the one from the last vertex back to the first.
+fn edges<T: Copy>(vertices: &[Point2<T>]) -> impl Iterator<Item = (Point2<T>, Point2<T>)> + '_ {
+    vertices
+        .iter()
+        .zip(vertices.iter().cycle().skip(1))
+        .map(|(&a, &b)| (a, b))
+}
+
+/// The area of a polygon with the [shoelace formula](https://en.wikipedia.org/wiki/Shoelace_formula).
+/// The vertices can be in either order, and the polygon is closed by an edge from the last vertex back to the first.
+///
+/// A polygon on a lattice can have half a unit of area, which is rounded down.
+///
+/// ```
+/// # use advent_of_code::geom::{polygon_area, Point2};
+/// let square = [(0, 0), (4, 0), (4, 4), (0, 4)].map(Point2::from);
+/// assert_eq!(polygon_area(&square), 16);
+/// ```
+pub fn polygon_area<T: Integer>(vertices: &[Point2<T>]) -> T {
+    double_area(vertices) / (T::ONE + T::ONE)
+}
+
+/// The number of lattice points on the edges of a polygon.
+/// For a polygon with only horizontal and vertical edges, this is the length of its boundary.
+///
+/// ```
+/// # use advent_of_code::geom::{boundary_points, Point2};
+/// let triangle = [(0, 0), (4, 0), (0, 2)].map(Point2::from);
+/// assert_eq!(boundary_points(&triangle), 8);
+/// ```
+pub fn boundary_points<T: Integer>(vertices: &[Point2<T>]) -> T {
+    edges(vertices).fold(T::ZERO, |points, (a, b)| {
+        points + math::gcd(a.x.distance(b.x), a.y.distance(b.y))
+    })
+}
+
+/// The number of lattice points strictly inside a polygon, with [Pick's theorem](https://en.wikipedia.org/wiki/Pick%27s_theorem).
+/// The vertices must be lattice points and the edges must not cross each other.
+///
+/// Adding [`boundary_points`] gives the number of tiles a loop on a grid covers, including the loop itself.
+///
+/// ```
+/// # use advent_of_code::geom::{interior_points, Point2};
+/// let square = [(0, 0), (4, 0), (4, 4), (0, 4)].map(Point2::from);
+/// assert_eq!(interior_points(&square), 9);
+/// ```
+pub fn interior_points<T: Integer>(vertices: &[Point2<T>]) -> T {
+    let two = T::ONE + T::ONE;
+    (double_area(vertices) + two - boundary_points(vertices)) / two
+}
+
+/// Whether a point is strictly inside of a polygon, by casting a ray from it and counting the edges it crosses.
+/// Points on the boundary are not inside.
+///
+/// ```
+/// # use advent_of_code::geom::{polygon_contains, Point2};
+/// let square = [(0, 0), (4, 0), (4, 4), (0, 4)].map(Point2::from);
+/// assert!(polygon_contains(&square, Point2::new(1, 2)));
+/// assert!(!polygon_contains(&square, Point2::new(4, 2)));
+/// assert!(!polygon_contains(&square, Point2::new(5, 2)));
+/// ```
+pub fn polygon_contains<T>(vertices: &[Point2<T>], point: Point2<T>) -> bool
+where
+    T: Integer + Neg<Output = T>,
+{
+    let mut inside = false;
+
+    for (a, b) in edges(vertices) {
+        // the sign of the cross product tells which side of the edge the point is on.
+        let cross = (b.x - a.x) * (point.y - a.y) - (b.y - a.y) * (point.x - a.x);
+
+        let on_edge = cross == T::ZERO
+            && (a.x.min(b.x)..=a.x.max(b.x)).contains(&point.x)
+            && (a.y.min(b.y)..=a.y.max(b.y)).contains(&point.y);
+
+        if on_edge {
+            return false;
+        }
+
+        // the ray goes to the right of the point, so it crosses edges that span its row and are to the right of it.
+        if (a.y > point.y) != (b.y > point.y) && (cross > T::ZERO) == (b.y > a.y) {
+            inside = !inside;
+        }
+    }
+
+    inside
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        boundary_points, interior_points, polygon_area, polygon_contains, Bounds, Direction,
+        Point2, Point3,
+    };
+
+    /// An L-shaped polygon, which is concave.
+    const L_SHAPE: [(i32, i32); 6] = [(0, 0), (4, 0), (4, 2), (2, 2), (2, 4), (0, 4)];
+
+    #[test]
+    fn turns_directions() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.reverse().reverse(), direction);
+            assert_eq!(direction.turn_right().turn_right(), direction.reverse());
+            assert_eq!(-direction.offset(), direction.reverse().offset());
+            assert_eq!(
+                direction.offset().rotate_right(),
+                direction.turn_right().offset()
+            );
+            assert_eq!(
+                direction.offset().rotate_left(),
+                direction.turn_left().offset()
+            );
+        }
+
+        assert_eq!(Direction::Left.turn_right(), Direction::Up);
+        assert!(Direction::Left.is_horizontal());
+        assert!(Direction::Up.is_vertical());
+    }
+
+    #[test]
+    fn parses_directions() {
+        assert_eq!(Direction::from_char('U'), Some(Direction::Up));
+        assert_eq!(Direction::from_char('>'), Some(Direction::Right));
+        assert_eq!(Direction::from_char('v'), Some(Direction::Down));
+        assert_eq!(Direction::from_char('x'), None);
+    }
+
+    #[test]
+    fn steps_points() {
+        let point = Point2::new(0_usize, 0);
+        assert_eq!(point.step(Direction::Left), None);
+        assert_eq!(point.step(Direction::Down), Some(Point2::new(0, 1)));
+        assert_eq!(point.step_by(Direction::Right, 5), Some(Point2::new(5, 0)));
+
+        let signed = Point2::new(0_i32, 0);
+        assert_eq!(signed.step_by(Direction::Up, 3), Some(Point2::new(0, -3)));
+        assert_eq!(Point2::new(0_u8, 255).step(Direction::Down), None);
+
+        let neighbors: Vec<_> = point.neighbors4().collect();
+        assert_eq!(neighbors, [Point2::new(1, 0), Point2::new(0, 1)]);
+    }
+
+    #[test]
+    fn steps_within_bounds() {
+        let bounds = Bounds::from_size(3_usize, 2);
+        let corner = Point2::new(2, 1);
+
+        assert_eq!(corner.step_within(Direction::Right, &bounds), None);
+        assert_eq!(corner.step_within(Direction::Down, &bounds), None);
+        assert_eq!(
+            corner.step_within(Direction::Up, &bounds),
+            Some(Point2::new(2, 0))
+        );
+
+        let bounds = Bounds::new(Point2::new(-1, -1), Point2::new(1, 1));
+        assert!(bounds.contains(Point2::new(-1, 0)));
+        assert!(!bounds.contains(Point2::new(1, 0)));
+    }
+
+    #[test]
+    fn computes_distances() {
+        assert_eq!(Point2::new(1_u32, 5).manhattan(Point2::new(4, 1)), 7);
+        assert_eq!(Point2::new(-2, 3).manhattan(Point2::new(2, -3)), 10);
+        assert_eq!(Point3::new(1_u64, 2, 3).manhattan(Point3::new(3, 2, 1)), 4);
+    }
+
+    #[test]
+    fn does_arithmetic() {
+        let mut point = Point2::new(1, 2);
+        point += Point2::new(3, 4);
+        assert_eq!(point, Point2::new(4, 6));
+        assert_eq!(point - Point2::new(4, 6), Point2::ORIGIN);
+        assert_eq!(-point * 2, Point2::new(-8, -12));
+
+        let mut point = Point3::from((1, 2, 3));
+        point -= Point3::new(1, 1, 1);
+        assert_eq!(<(i32, i32, i32)>::from(point), (0, 1, 2));
+        assert_eq!(Point2::new(1, 2).to_string(), "(1, 2)");
+    }
+
+    #[test]
+    fn measures_polygons() {
+        let polygon = L_SHAPE.map(Point2::from);
+        assert_eq!(polygon_area(&polygon), 12);
+        assert_eq!(boundary_points(&polygon), 16);
+        assert_eq!(interior_points(&polygon), 5);
+
+        let mut reversed = polygon;
+        reversed.reverse();
+        assert_eq!(polygon_area(&reversed), 12);
+        assert_eq!(interior_points(&reversed), 5);
+
+        let unsigned = L_SHAPE.map(|(x, y)| Point2::new(x as usize, y as usize));
+        assert_eq!(polygon_area(&unsigned), 12);
+        assert_eq!(interior_points(&unsigned), 5);
+
+        let triangle = [(0, 0), (3, 0), (0, 3)].map(Point2::from);
+        assert_eq!(polygon_area(&triangle), 4);
+        assert_eq!(boundary_points(&triangle), 9);
+        assert_eq!(interior_points(&triangle), 1);
+    }
+
+    #[test]
+    fn finds_points_in_polygons() {
+        let polygon = L_SHAPE.map(Point2::from);
+
+        let inside: Vec<_> = (-1..6)
+            .flat_map(|y| (-1..6).map(move |x| Point2::new(x, y)))
+            .filter(|&point| polygon_contains(&polygon, point))
+            .collect();
+
+        assert_eq!(inside.len(), 5);
+        assert!(inside.contains(&Point2::new(1, 3)));
+        assert!(!inside.contains(&Point2::new(3, 3)));
+        assert!(!polygon_contains(&polygon, Point2::new(2, 3)));
+        assert!(!polygon_contains(&polygon, Point2::new(0, 0)));
+    }
+}
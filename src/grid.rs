@@ -0,0 +1,510 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::geom::{Bounds, Direction, Point2};
+
+/// A rectangular grid of cells, stored row by row in a single [`Vec`].
+///
+/// Grids are usually parsed from puzzle input, one row per line.
+/// Cells are addressed by a [`Point2`], where `x` is the column and `y` the row.
+///
+/// ```
+/// # use advent_of_code::{geom::Point2, grid::Grid};
+/// let grid: Grid<u8> = "#.S\n...".parse().unwrap();
+/// assert_eq!((grid.rows(), grid.cols()), (2, 3));
+/// assert_eq!(grid[Point2::new(1, 0)], b'.');
+/// assert_eq!(grid.find(&b'S'), Some(Point2::new(2, 0)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells stored row by row.
+    ///
+    /// # Panics
+    /// Panics if the number of cells is not `rows * cols`.
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            rows * cols,
+            "expecting {rows}x{cols} cells, got {}",
+            cells.len()
+        );
+        Self { cells, rows, cols }
+    }
+
+    /// Creates a grid by calling `f` with the position of every cell.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(Point2<usize>) -> T) -> Self {
+        let cells = (0..rows)
+            .flat_map(|y| (0..cols).map(move |x| Point2::new(x, y)))
+            .map(&mut f)
+            .collect();
+        Self { cells, rows, cols }
+    }
+
+    /// Parses a grid with one row per line, converting every character with `f`.
+    /// Trailing line breaks are ignored.
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        let mut cells = vec![];
+        let mut rows = 0;
+        let mut cols = None;
+
+        for (index, line) in input.trim_end_matches(['\n', '\r']).lines().enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let found = cells.len() - len;
+
+            match cols {
+                None => cols = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(ParseGridError::Ragged {
+                        line: index + 1,
+                        expected,
+                        found,
+                    })
+                }
+                _ => {}
+            }
+
+            rows += 1;
+        }
+
+        match cols {
+            Some(cols) if cols > 0 => Ok(Self { cells, rows, cols }),
+            _ => Err(ParseGridError::Empty),
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// The rectangle of points inside of the grid, to step through it with [`Point2::step_within`].
+    pub fn bounds(&self) -> Bounds<usize> {
+        Bounds::from_size(self.cols, self.rows)
+    }
+
+    /// Returns `true` if the position is inside of the grid.
+    pub fn contains(&self, position: Point2<usize>) -> bool {
+        position.x < self.cols && position.y < self.rows
+    }
+
+    pub fn get(&self, position: Point2<usize>) -> Option<&T> {
+        self.index_of(position).map(|index| &self.cells[index])
+    }
+
+    pub fn get_mut(&mut self, position: Point2<usize>) -> Option<&mut T> {
+        self.index_of(position).map(|index| &mut self.cells[index])
+    }
+
+    /// Returns the position `offset` away from `position`, if it is inside of the grid.
+    ///
+    /// ```
+    /// # use advent_of_code::{geom::Point2, grid::Grid};
+    /// let grid = Grid::from_vec(2, 2, vec![0; 4]);
+    /// assert_eq!(grid.offset(Point2::ORIGIN, Point2::new(1, 1)), Some(Point2::new(1, 1)));
+    /// assert_eq!(grid.offset(Point2::ORIGIN, Point2::new(0, -1)), None);
+    /// ```
+    pub fn offset(&self, position: Point2<usize>, offset: Point2<isize>) -> Option<Point2<usize>> {
+        let position = Point2::new(
+            position.x.checked_add_signed(offset.x)?,
+            position.y.checked_add_signed(offset.y)?,
+        );
+        self.contains(position).then_some(position)
+    }
+
+    /// Returns the positions of the up to 4 orthogonal neighbors of a cell that are inside of the grid,
+    /// clockwise from the one above.
+    pub fn neighbors4(&self, position: Point2<usize>) -> impl Iterator<Item = Point2<usize>> + '_ {
+        let bounds = self.bounds();
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| position.step_within(direction, &bounds))
+    }
+
+    /// Returns the positions of the up to 8 neighbors of a cell that are inside of the grid, diagonals included,
+    /// clockwise from the one above.
+    pub fn neighbors8(&self, position: Point2<usize>) -> impl Iterator<Item = Point2<usize>> + '_ {
+        // every direction is followed by the diagonal between it and the next direction clockwise.
+        Direction::ALL
+            .into_iter()
+            .flat_map(|direction| {
+                let offset = direction.offset();
+                [offset, offset + direction.turn_right().offset()]
+            })
+            .filter_map(move |offset| self.offset(position, offset))
+    }
+
+    /// Returns the cells of a row.
+    ///
+    /// # Panics
+    /// Panics if the row is out of bounds.
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Returns the cells of a row for modification.
+    ///
+    /// # Panics
+    /// Panics if the row is out of bounds.
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    /// Returns the cells of a column, from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if the column is out of bounds.
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(col < self.cols, "column {col} is out of bounds");
+        (0..self.rows).map(move |row| &self.cells[row * self.cols + col])
+    }
+
+    /// Returns every row, from top to bottom.
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        // grids without columns have no cells, `max` keeps `chunks_exact` from panicking on them.
+        self.cells.chunks_exact(self.cols.max(1))
+    }
+
+    /// Returns every column, from left to right.
+    pub fn iter_columns(&self) -> impl DoubleEndedIterator<Item = impl Iterator<Item = &T>> + '_ {
+        (0..self.cols).map(|col| self.column(col))
+    }
+
+    /// Returns the position of every cell, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Point2<usize>> {
+        let cols = self.cols;
+        (0..self.rows).flat_map(move |y| (0..cols).map(move |x| Point2::new(x, y)))
+    }
+
+    /// Returns every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point2<usize>, &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Returns every cell for modification, row by row.
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> + '_ {
+        self.cells.iter_mut()
+    }
+
+    /// Returns the position of the first cell equal to `value`, row by row.
+    pub fn find(&self, value: &T) -> Option<Point2<usize>>
+    where
+        T: PartialEq,
+    {
+        self.find_all(value).next()
+    }
+
+    /// Returns the positions of all cells equal to `value`, row by row.
+    pub fn find_all<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Point2<usize>> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(position, _)| position)
+    }
+
+    /// Creates a grid of the same size by converting every cell with `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+
+    fn index_of(&self, position: Point2<usize>) -> Option<usize> {
+        self.contains(position)
+            .then(|| position.y * self.cols + position.x)
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    pub fn new(rows: usize, cols: usize, value: T) -> Self {
+        Self::from_vec(rows, cols, vec![value; rows * cols])
+    }
+
+    /// Swaps rows and columns, i.e. mirrors the grid along its main diagonal.
+    ///
+    /// ```
+    /// # use advent_of_code::grid::Grid;
+    /// let grid: Grid<char> = "ab\ncd\nef".parse().unwrap();
+    /// assert_eq!(grid.transpose().to_string(), "ace\nbdf");
+    /// ```
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |p| {
+            self[Point2::new(p.y, p.x)].clone()
+        })
+    }
+
+    /// Rotates the grid by 90 degrees, so that the first column becomes the first row in reverse.
+    ///
+    /// ```
+    /// # use advent_of_code::grid::Grid;
+    /// let grid: Grid<char> = "ab\ncd\nef".parse().unwrap();
+    /// assert_eq!(grid.rotate_clockwise().to_string(), "eca\nfdb");
+    /// ```
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |p| {
+            self[Point2::new(p.y, self.rows - 1 - p.x)].clone()
+        })
+    }
+
+    /// Rotates the grid by 90 degrees, so that the last column becomes the first row.
+    #[must_use]
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |p| {
+            self[Point2::new(self.cols - 1 - p.y, p.x)].clone()
+        })
+    }
+
+    /// Mirrors the grid left to right, reversing every row.
+    #[must_use]
+    pub fn flip_horizontal(&self) -> Self {
+        Self::from_fn(self.rows, self.cols, |p| {
+            self[Point2::new(self.cols - 1 - p.x, p.y)].clone()
+        })
+    }
+
+    /// Mirrors the grid top to bottom, reversing every column.
+    #[must_use]
+    pub fn flip_vertical(&self) -> Self {
+        Self::from_fn(self.rows, self.cols, |p| {
+            self[Point2::new(p.x, self.rows - 1 - p.y)].clone()
+        })
+    }
+}
+
+impl<T> Index<Point2<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Point2<usize>) -> &Self::Output {
+        let index = self
+            .index_of(position)
+            .unwrap_or_else(|| panic!("position {position} is out of bounds"));
+        &self.cells[index]
+    }
+}
+
+impl<T> IndexMut<Point2<usize>> for Grid<T> {
+    fn index_mut(&mut self, position: Point2<usize>) -> &mut Self::Output {
+        let index = self
+            .index_of(position)
+            .unwrap_or_else(|| panic!("position {position} is out of bounds"));
+        &mut self.cells[index]
+    }
+}
+
+/// Displays the grid with one line per row, without a trailing line break.
+/// Cells are displayed without separators, so a `Grid<char>` displays as the input it was parsed from.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, row) in self.iter_rows().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c| c)
+    }
+}
+
+/// Parses a grid of ASCII bytes. Characters outside of ASCII are replaced with `?`.
+impl FromStr for Grid<u8> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c| {
+            u8::try_from(c).ok().filter(u8::is_ascii).unwrap_or(b'?')
+        })
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// The input has no cells.
+    Empty,
+    /// A line has a different length than the first one.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Empty => f.write_str("expecting a grid, the input is empty"),
+            ParseGridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expecting {expected} cells per row, line {line} has {found}"
+            ),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, ParseGridError};
+    use crate::geom::Point2;
+
+    fn grid(s: &str) -> Grid<char> {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid: Grid<u8> = "#.\n.#\n".parse().unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (2, 2));
+        assert_eq!(grid.row(1), b".#");
+        assert_eq!(grid.map(|&b| char::from(b)).to_string(), "#.\n.#");
+
+        let digits = Grid::parse_with("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits[Point2::new(0, 1)], 3);
+    }
+
+    #[test]
+    fn rejects_invalid_grids() {
+        assert_eq!("".parse::<Grid<char>>(), Err(ParseGridError::Empty));
+        assert_eq!("\n\n".parse::<Grid<char>>(), Err(ParseGridError::Empty));
+        assert_eq!(
+            "ab\nabc".parse::<Grid<char>>(),
+            Err(ParseGridError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let grid = grid("ab\ncd");
+        assert_eq!(grid.get(Point2::new(1, 1)), Some(&'d'));
+        assert_eq!(grid.get(Point2::new(0, 2)), None);
+        assert_eq!(grid.get(Point2::new(2, 0)), None);
+        assert!(grid.contains(Point2::new(1, 1)));
+        assert!(!grid.contains(Point2::new(2, 1)));
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let grid = Grid::new(3, 3, 0);
+
+        let corner: Vec<_> = grid.neighbors4(Point2::ORIGIN).collect();
+        assert_eq!(corner, [Point2::new(1, 0), Point2::new(0, 1)]);
+
+        let center: Vec<_> = grid.neighbors4(Point2::new(1, 1)).collect();
+        assert_eq!(
+            center,
+            [
+                Point2::new(1, 0),
+                Point2::new(2, 1),
+                Point2::new(1, 2),
+                Point2::new(0, 1)
+            ]
+        );
+
+        let corner: Vec<_> = grid.neighbors8(Point2::ORIGIN).collect();
+        assert_eq!(
+            corner,
+            [Point2::new(1, 0), Point2::new(1, 1), Point2::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors8(Point2::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8(Point2::new(1, 2)).count(), 5);
+    }
+
+    #[test]
+    fn views_rows_and_columns() {
+        let grid = grid("ab\ncd\nef");
+
+        let column: String = grid.column(1).collect();
+        assert_eq!(column, "bdf");
+
+        let rows: Vec<String> = grid.iter_rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, ["ab", "cd", "ef"]);
+
+        let columns: Vec<String> = grid.iter_columns().map(Iterator::collect).collect();
+        assert_eq!(columns, ["ace", "bdf"]);
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = grid("ab\ncd\nef");
+
+        assert_eq!(grid.transpose().to_string(), "ace\nbdf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "eca\nfdb");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "bdf\nace");
+        assert_eq!(grid.flip_horizontal().to_string(), "ba\ndc\nfe");
+        assert_eq!(grid.flip_vertical().to_string(), "ef\ncd\nab");
+
+        let rotated = (0..4).fold(grid.clone(), |g, _| g.rotate_clockwise());
+        assert_eq!(rotated, grid);
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn finds_values() {
+        let grid = grid("S.#\n#.S");
+        assert_eq!(grid.find(&'S'), Some(Point2::ORIGIN));
+        assert_eq!(grid.find(&'x'), None);
+
+        let walls: Vec<_> = grid.find_all(&'#').collect();
+        assert_eq!(walls, [Point2::new(2, 0), Point2::new(0, 1)]);
+    }
+
+    #[test]
+    fn indexes_with_points() {
+        let grid = grid("ab\ncd\nef");
+        assert_eq!(grid[Point2::new(1, 2)], 'f');
+        assert!(grid.bounds().contains(Point2::new(1, 2)));
+        assert!(!grid.bounds().contains(Point2::new(2, 1)));
+    }
+
+    #[test]
+    fn maps_and_modifies_cells() {
+        let mut grid = grid("ab\ncd");
+        grid[Point2::ORIGIN] = 'x';
+        grid.row_mut(1)[1] = 'y';
+        assert_eq!(grid.to_string(), "xb\ncy");
+
+        let upper = grid.map(char::to_ascii_uppercase);
+        assert_eq!(upper.to_string(), "XB\nCY");
+    }
+}
@@ -1,4 +1,14 @@
+mod answer;
 mod day;
+pub mod geom;
+pub mod grid;
+pub mod math;
+mod part;
+pub mod search;
 pub mod template;
+mod year;
 
+pub use answer::*;
 pub use day::*;
+pub use part::*;
+pub use year::*;
@@ -1,64 +1,168 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use std::process::ExitCode;
+
+use advent_of_code::template::commands::{
+    all, compare, download, extract, profile, read, scaffold, solve, watch,
+};
 use args::{parse, AppArguments};
 
-mod args {
-    use std::process;
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
 
-    use advent_of_code::Day;
+mod args {
+    use advent_of_code::{
+        part,
+        template::{
+            limits::{self, Limits},
+            runner::{InputSource, OutputFormat},
+        },
+        Day, Part, Year,
+    };
 
     pub enum AppArguments {
         Download {
             day: Day,
+            year: Option<Year>,
         },
         Read {
             day: Day,
+            year: Option<Year>,
+        },
+        Extract {
+            day: Day,
+            year: Option<Year>,
         },
         Scaffold {
             day: Day,
+            year: Option<Year>,
+            template: Option<String>,
         },
         Solve {
             day: Day,
+            year: Option<Year>,
             release: bool,
             time: bool,
-            submit: Option<u8>,
+            submit: Option<Part>,
+            format: OutputFormat,
+            record: bool,
+            input: InputSource,
+            part: Option<Part>,
+            limits: Limits,
+            count_allocations: bool,
         },
         All {
+            year: Option<Year>,
+            all_years: bool,
             release: bool,
             time: bool,
+            format: OutputFormat,
+            isolated: bool,
+            parallel: bool,
+            record: bool,
+            part: Option<Part>,
+            limits: Limits,
+            count_allocations: bool,
+        },
+        Watch {
+            day: Day,
+            year: Option<Year>,
+            release: bool,
+        },
+        Compare {
+            year: Option<Year>,
+            baseline: Option<String>,
+            threshold: f64,
+        },
+        Profile {
+            day: Day,
+            year: Option<Year>,
+            part: Part,
+            input: InputSource,
         },
     }
 
+    /// Parses `--timeout <duration>` and `--max-memory <size>`.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            timeout: args.opt_value_from_fn("--timeout", limits::parse_duration)?,
+            max_memory: args.opt_value_from_fn("--max-memory", limits::parse_bytes)?,
+        })
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let mut raw_args: Vec<String> = std::env::args().skip(1).collect();
+
+        // `--example` takes an optional number, which pico_args can't tell apart from the day.
+        let input = match raw_args.first().map(String::as_str) {
+            Some("solve" | "profile") => InputSource::from_args(&mut raw_args)?,
+            _ => InputSource::Puzzle,
+        };
+
+        let mut args =
+            pico_args::Arguments::from_vec(raw_args.into_iter().map(Into::into).collect());
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: args.opt_value_from_str("--year")?,
+                all_years: args.contains("--all-years"),
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                isolated: args.contains("--isolated"),
+                parallel: args.contains("--parallel"),
+                record: args.contains("--record"),
+                part: args.opt_value_from_str("--part")?,
+                limits: parse_limits(&mut args)?,
+                count_allocations: args.contains("--allocations"),
+            },
+            Some("compare") => AppArguments::Compare {
+                year: args.opt_value_from_str("--year")?,
+                baseline: args.opt_value_from_str("--baseline")?,
+                threshold: args.opt_value_from_str("--threshold")?.unwrap_or(10.0),
             },
             Some("download") => AppArguments::Download {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
             },
+            Some("extract") => AppArguments::Extract {
+                year: args.opt_value_from_str("--year")?,
+                day: args.free_from_str()?,
+            },
+            Some("profile") => AppArguments::Profile {
+                year: args.opt_value_from_str("--year")?,
+                day: args.free_from_str()?,
+                part: args.opt_value_from_str("--part")?.unwrap_or(part!(1)),
+                input,
+            },
             Some("read") => AppArguments::Read {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
+                year: args.opt_value_from_str("--year")?,
+                template: args.opt_value_from_str("--template")?,
                 day: args.free_from_str()?,
             },
             Some("solve") => AppArguments::Solve {
+                year: args.opt_value_from_str("--year")?,
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                record: args.contains("--record"),
+                part: args.opt_value_from_str("--part")?,
+                limits: parse_limits(&mut args)?,
+                count_allocations: args.contains("--allocations"),
+                input,
+            },
+            Some("watch") => AppArguments::Watch {
+                year: args.opt_value_from_str("--year")?,
+                day: args.free_from_str()?,
+                release: args.contains("--release"),
             },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some(x) => return Err(format!("unknown command: {x}").into()),
+            None => return Err("no command specified".into()),
         };
 
         let remaining = args.finish();
@@ -70,23 +174,97 @@ mod args {
     }
 }
 
-fn main() {
-    match parse() {
+/// Invalid command-line arguments exit with the same code as `commands::Error::Usage`.
+const USAGE_EXIT_CODE: u8 = 2;
+
+fn main() -> ExitCode {
+    let args = match parse() {
+        Ok(args) => args,
         Err(err) => {
             eprintln!("Error: {err}");
-            std::process::exit(1);
+            return ExitCode::from(USAGE_EXIT_CODE);
         }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
-        },
     };
+
+    let result = match args {
+        AppArguments::All {
+            year,
+            all_years,
+            release,
+            time,
+            format,
+            isolated,
+            parallel,
+            record,
+            part,
+            limits,
+            count_allocations,
+        } => all::handle(
+            year,
+            all_years,
+            release,
+            time,
+            format,
+            isolated,
+            parallel,
+            record,
+            part,
+            limits,
+            count_allocations,
+            solutions::SOLUTIONS,
+        ),
+        AppArguments::Compare {
+            year,
+            baseline,
+            threshold,
+        } => compare::handle(year, baseline, threshold),
+        AppArguments::Watch { day, year, release } => watch::handle(year, day, release),
+        AppArguments::Download { day, year } => download::handle(year, day),
+        AppArguments::Profile {
+            day,
+            year,
+            part,
+            input,
+        } => profile::handle(year, day, part, &input),
+        AppArguments::Read { day, year } => read::handle(year, day),
+        AppArguments::Extract { day, year } => extract::handle(year, day),
+        AppArguments::Scaffold {
+            day,
+            year,
+            template,
+        } => scaffold::handle(year, day, template),
+        AppArguments::Solve {
+            day,
+            year,
+            release,
+            time,
+            submit,
+            format,
+            record,
+            input,
+            part,
+            limits,
+            count_allocations,
+        } => solve::handle(
+            year,
+            day,
+            release,
+            time,
+            submit,
+            format,
+            record,
+            &input,
+            part,
+            limits,
+            count_allocations,
+        ),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Error: {err}");
+            ExitCode::from(err.exit_code())
+        }
+    }
 }
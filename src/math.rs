@@ -0,0 +1,279 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// An integer type that can be used as the coordinate of a [`Point2`](crate::geom::Point2) or [`Point3`](crate::geom::Point3).
+/// Implemented for every primitive integer type.
+pub trait Coordinate:
+    Copy
+    + Debug
+    + Default
+    + Eq
+    + Ord
+    + Hash
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+
+    /// The absolute difference of two values, without overflowing for unsigned types.
+    fn distance(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                fn checked_add(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_add(self, rhs)
+                }
+
+                fn checked_sub(self, rhs: Self) -> Option<Self> {
+                    <$t>::checked_sub(self, rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// An integer type that [`gcd`] and [`lcm`] work with.
+/// Implemented for every primitive integer type.
+pub trait Integer: Coordinate + Div<Output = Self> + Rem<Output = Self> {}
+
+impl<T: Coordinate + Div<Output = T> + Rem<Output = T>> Integer for T {}
+
+/// The greatest common divisor of two integers, which is never negative. `gcd(0, 0)` is `0`.
+///
+/// ```
+/// # use advent_of_code::math::gcd;
+/// assert_eq!(gcd(12_u32, 18), 6);
+/// assert_eq!(gcd(-4_i64, 6), 2);
+/// ```
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let (mut a, mut b) = (a.distance(T::ZERO), b.distance(T::ZERO));
+
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// The least common multiple of two integers, which is never negative. It is `0` if either of them is `0`.
+///
+/// ```
+/// # use advent_of_code::math::lcm;
+/// assert_eq!(lcm(4_u32, 6), 12);
+/// ```
+pub fn lcm<T: Integer>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+
+    (a / gcd(a, b) * b).distance(T::ZERO)
+}
+
+/// The greatest common divisor of all values, `0` if there are none.
+pub fn gcd_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ZERO, gcd)
+}
+
+/// The least common multiple of all values, `1` if there are none.
+///
+/// ```
+/// # use advent_of_code::math::lcm_all;
+/// assert_eq!(lcm_all([2_usize, 3, 4]), 12);
+/// ```
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> T {
+    values.into_iter().fold(T::ONE, lcm)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns `(g, x, y)` such that `a * x + b * y = g`, where `g` is the [`gcd`] of `a` and `b`.
+///
+/// ```
+/// # use advent_of_code::math::extended_gcd;
+/// let (g, x, y) = extended_gcd(240, 46);
+/// assert_eq!(g, 2);
+/// assert_eq!(240 * x + 46 * y, 2);
+/// ```
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The inverse of `a` modulo `modulus`, i.e. the `x` in `0..modulus` with `a * x ≡ 1`.
+/// Returns [`None`] if `a` and `modulus` are not coprime.
+///
+/// ```
+/// # use advent_of_code::math::mod_inverse;
+/// assert_eq!(mod_inverse(3, 11), Some(4));
+/// assert_eq!(mod_inverse(2, 4), None);
+/// ```
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese Remainder Theorem.
+/// The moduli don't need to be coprime.
+///
+/// Returns the smallest non-negative solution and the [`lcm`] of the moduli, since every solution is congruent modulo that.
+/// Returns [`None`] if the congruences contradict each other or the lcm doesn't fit into an `i64`.
+///
+/// ```
+/// # use advent_of_code::math::crt;
+/// assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+/// assert_eq!(crt([(1, 4), (3, 6)]), Some((9, 12)));
+/// assert_eq!(crt([(0, 4), (1, 6)]), None);
+/// ```
+///
+/// # Panics
+///
+/// If a modulus is not positive.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    let (mut residue, mut modulus) = (0_i128, 1_i128);
+
+    for (r, m) in congruences {
+        assert!(m > 0, "modulus must be positive, got {m}");
+        let (r, m) = (i128::from(r).rem_euclid(i128::from(m)), i128::from(m));
+
+        let (g, p, _) = extended_gcd(i64::try_from(modulus).ok()?, i64::try_from(m).ok()?);
+        let g = i128::from(g);
+
+        if (r - residue) % g != 0 {
+            return None;
+        }
+
+        // `modulus * p ≡ g (mod m)`, so stepping `k` times by `modulus` moves the residue to `r`.
+        let m_g = m / g;
+        let k = ((r - residue) / g % m_g * i128::from(p)).rem_euclid(m_g);
+
+        residue += modulus * k;
+        modulus *= m_g;
+        residue = residue.rem_euclid(modulus);
+    }
+
+    Some((i64::try_from(residue).ok()?, i64::try_from(modulus).ok()?))
+}
+
+/// Computes `base.pow(exp) % modulus` without overflowing.
+/// Returns [`None`] if `modulus` is `0`.
+///
+/// ```
+/// # use advent_of_code::math::mod_pow;
+/// assert_eq!(mod_pow(4, 13, 497), Some(445));
+/// assert_eq!(mod_pow(2, 10, 0), None);
+/// ```
+pub fn mod_pow(base: u64, exp: u64, modulus: u64) -> Option<u64> {
+    if modulus == 0 {
+        return None;
+    }
+
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut exp = exp;
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exp >>= 1;
+    }
+
+    u64::try_from(result).ok()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{crt, extended_gcd, gcd, gcd_all, lcm, lcm_all, mod_inverse, mod_pow};
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(0_u8, 0), 0);
+        assert_eq!(gcd(0_u8, 5), 5);
+        assert_eq!(gcd(-12_i32, -18), 6);
+        assert_eq!(lcm(0_u64, 5), 0);
+        assert_eq!(lcm(-4_i32, 6), 12);
+        assert_eq!(gcd_all([12_usize, 18, 27]), 3);
+        assert_eq!(gcd_all(Vec::<usize>::new()), 0);
+        assert_eq!(lcm_all(Vec::<usize>::new()), 1);
+        assert_eq!(lcm_all([3739_usize, 3761, 3797, 3889]), 207_652_583_562_007);
+    }
+
+    #[test]
+    fn computes_bezout_coefficients() {
+        for (a, b) in [
+            (240, 46),
+            (46, 240),
+            (-7, 3),
+            (7, -3),
+            (0, 5),
+            (5, 0),
+            (0, 0),
+        ] {
+            let (g, x, y) = extended_gcd(a, b);
+            assert_eq!(g, gcd(a, b), "gcd of {a} and {b}");
+            assert_eq!(a * x + b * y, g, "coefficients of {a} and {b}");
+        }
+
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt([]), Some((0, 1)));
+        assert_eq!(crt([(-1, 5)]), Some((4, 5)));
+        assert_eq!(crt([(0, 6), (0, 10), (0, 15)]), Some((0, 30)));
+        assert_eq!(crt([(3, 6), (7, 10)]), Some((27, 30)));
+        assert_eq!(crt([(3, 6), (4, 10)]), None);
+        assert_eq!(
+            crt([(1, 1_000_000_007), (2, 998_244_353)]),
+            Some((993_328_913_953_302_350, 998_244_359_987_710_471))
+        );
+        assert_eq!(crt([(0, i64::MAX), (0, i64::MAX - 1)]), None);
+    }
+
+    #[test]
+    fn computes_modular_powers() {
+        assert_eq!(mod_pow(0, 0, 7), Some(1));
+        assert_eq!(mod_pow(5, 0, 1), Some(0));
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), Some(1));
+        assert_eq!(mod_pow(3, 200, 1_000_000_007), Some(136_318_165));
+    }
+}
@@ -0,0 +1,293 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::hash::Hash;
+use std::iter;
+use std::ops::Add;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// A state that was expanded during a search, linked to the state it was reached from.
+#[derive(Debug, Clone)]
+struct Node<S, C> {
+    state: S,
+    cost: C,
+    parent: Option<usize>,
+}
+
+/// The result of a search that reached a goal.
+/// The path to the goal is only reconstructed when [`Found::path`] is called.
+#[derive(Debug, Clone)]
+pub struct Found<S, C> {
+    /// The total cost of the path to the goal. For [`bfs`], the number of steps.
+    pub cost: C,
+    nodes: Vec<Node<S, C>>,
+    goal: usize,
+}
+
+impl<S: Clone, C> Found<S, C> {
+    /// The state that satisfied the goal.
+    pub fn goal(&self) -> &S {
+        &self.nodes[self.goal].state
+    }
+
+    /// The states on the path from the start to the goal, both included.
+    pub fn path(&self) -> Vec<S> {
+        let mut path: Vec<S> = iter::successors(Some(self.goal), |&i| self.nodes[i].parent)
+            .map(|i| self.nodes[i].state.clone())
+            .collect();
+
+        path.reverse();
+        path
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Finds the path with the fewest steps from `start` to the first state that satisfies `is_goal`.
+/// `successors` returns the states that can be reached from a state in one step.
+///
+/// ```
+/// # use advent_of_code::search::bfs;
+/// let found = bfs(1, |&n| [n + 1, n * 2], |&n| n == 10).unwrap();
+/// assert_eq!(found.cost, 4);
+/// assert_eq!(found.path(), [1, 2, 4, 5, 10]);
+/// ```
+pub fn bfs<S, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut seen = FxHashSet::default();
+    seen.insert(start.clone());
+
+    let mut nodes = vec![Node {
+        state: start,
+        cost: 0,
+        parent: None,
+    }];
+    let mut queue = VecDeque::from([0]);
+
+    while let Some(i) = queue.pop_front() {
+        if is_goal(&nodes[i].state) {
+            let cost = nodes[i].cost;
+            return Some(Found {
+                cost,
+                nodes,
+                goal: i,
+            });
+        }
+
+        let cost = nodes[i].cost + 1;
+
+        for next in successors(&nodes[i].state) {
+            if seen.insert(next.clone()) {
+                queue.push_back(nodes.len());
+                nodes.push(Node {
+                    state: next,
+                    cost,
+                    parent: Some(i),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns the number of steps to every state that can be reached from `start`, including `start` itself.
+///
+/// ```
+/// # use advent_of_code::search::distances;
+/// let distances = distances(0, |&n| (n < 3).then_some(n + 1));
+/// assert_eq!(distances.len(), 4);
+/// assert_eq!(distances[&3], 3);
+/// ```
+pub fn distances<S, I>(start: S, mut successors: impl FnMut(&S) -> I) -> FxHashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = FxHashMap::default();
+    distances.insert(start.clone(), 0);
+
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((state, steps)) = queue.pop_front() {
+        for next in successors(&state) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Finds the cheapest path from `start` to the first state that satisfies `is_goal`.
+/// `successors` returns the states that can be reached from a state, with the cost of getting there.
+/// Costs must not be negative, and `C::default()` must be zero.
+///
+/// ```
+/// # use advent_of_code::search::dijkstra;
+/// let edges = |&n: &u32| [(n + 1, 1), (n * 3, 3)];
+/// let found = dijkstra(1, edges, |&n| n == 9).unwrap();
+/// assert_eq!((found.cost, found.path()), (5, vec![1, 2, 3, 9]));
+/// ```
+pub fn dijkstra<S, C, I>(
+    start: S,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but expands the states with the lowest cost plus `heuristic` first.
+/// `heuristic` estimates the remaining cost to a goal. If it never overestimates, the found path is the cheapest one.
+///
+/// ```
+/// # use advent_of_code::search::astar;
+/// let target = 9_u32;
+/// let edges = |&n: &u32| [(n + 1, 1), (n * 3, 3)];
+/// let found = astar(1, edges, |&n| target.saturating_sub(n).min(1), |&n| n == target).unwrap();
+/// assert_eq!(found.cost, 5);
+/// ```
+pub fn astar<S, C, I>(
+    start: S,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut best = FxHashMap::default();
+    best.insert(start.clone(), C::default());
+
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), 0))]);
+    let mut nodes = vec![Node {
+        state: start,
+        cost: C::default(),
+        parent: None,
+    }];
+
+    while let Some(Reverse((_, i))) = heap.pop() {
+        let Node { state, cost, .. } = &nodes[i];
+        let cost = *cost;
+
+        // a cheaper way to this state was found after this node was queued.
+        if best.get(state).is_some_and(|&best| cost > best) {
+            continue;
+        }
+
+        if is_goal(state) {
+            return Some(Found {
+                cost,
+                nodes,
+                goal: i,
+            });
+        }
+
+        for (next, step_cost) in successors(state) {
+            let next_cost = cost + step_cost;
+
+            if best.get(&next).is_some_and(|&best| next_cost >= best) {
+                continue;
+            }
+
+            best.insert(next.clone(), next_cost);
+            heap.push(Reverse((next_cost + heuristic(&next), nodes.len())));
+            nodes.push(Node {
+                state: next,
+                cost: next_cost,
+                parent: Some(i),
+            });
+        }
+    }
+
+    None
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, dijkstra, distances};
+
+    /// A 5x5 maze as `(row, column)`, walls are `#`.
+    const MAZE: [&[u8]; 5] = [b"S..#.", b"##.#.", b".....", b".###.", b"...#E"];
+
+    fn neighbors((row, col): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        [(0, 1), (1, 0), (0, -1), (-1, 0)]
+            .into_iter()
+            .filter_map(move |(dr, dc)| {
+                let row = row.checked_add_signed(dr)?;
+                let col = col.checked_add_signed(dc)?;
+                (MAZE.get(row)?.get(col)? != &b'#').then_some((row, col))
+            })
+    }
+
+    #[test]
+    fn finds_fewest_steps() {
+        let found = bfs((0, 0), |&p| neighbors(p), |&p| p == (4, 4)).unwrap();
+        assert_eq!(found.cost, 8);
+        assert_eq!(found.goal(), &(4, 4));
+        assert_eq!(
+            found.path(),
+            [
+                (0, 0),
+                (0, 1),
+                (0, 2),
+                (1, 2),
+                (2, 2),
+                (2, 3),
+                (2, 4),
+                (3, 4),
+                (4, 4)
+            ]
+        );
+
+        let start = bfs((0, 0), |&p| neighbors(p), |&p| p == (0, 0)).unwrap();
+        assert_eq!((start.cost, start.path()), (0, vec![(0, 0)]));
+
+        assert!(bfs((0, 0), |&p| neighbors(p), |&p| p == (4, 3)).is_none());
+    }
+
+    #[test]
+    fn finds_all_distances() {
+        let distances = distances((0, 0), |&p| neighbors(p));
+        assert_eq!(distances.len(), 17);
+        assert_eq!(distances[&(0, 0)], 0);
+        assert_eq!(distances[&(0, 4)], 8);
+        assert_eq!(distances[&(4, 2)], 10);
+        assert!(!distances.contains_key(&(4, 3)));
+    }
+
+    #[test]
+    fn finds_cheapest_path() {
+        // going through `b` takes more steps, but is cheaper.
+        let edges = |&node: &char| match node {
+            'a' => vec![('b', 1), ('d', 10)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        };
+
+        let found = dijkstra('a', edges, |&n| n == 'd').unwrap();
+        assert_eq!((found.cost, found.path()), (3, vec!['a', 'b', 'c', 'd']));
+
+        let found = astar('a', edges, |&n| u32::from(n != 'd'), |&n| n == 'd').unwrap();
+        assert_eq!((found.cost, found.path()), (3, vec!['a', 'b', 'c', 'd']));
+
+        assert!(dijkstra('b', edges, |&n| n == 'a').is_none());
+    }
+}
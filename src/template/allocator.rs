@@ -0,0 +1,166 @@
+/// Module that counts heap allocations, so the runner can report how much memory a part allocates.
+/// The counting allocator is opt-in: it is only installed as the global allocator when the
+/// `count_allocations` feature is enabled, otherwise no allocations are measured.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::limits::format_bytes;
+
+#[cfg(feature = "count_allocations")]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+static PEAK_LIVE_BYTES: AtomicU64 = AtomicU64::new(0);
+
+/// A global allocator that forwards to the system allocator and counts every allocation.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn on_alloc(size: usize) {
+        let size = size as u64;
+        ALLOCATED_BYTES.fetch_add(size, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_LIVE_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn on_dealloc(size: usize) {
+        LIVE_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+}
+
+// SAFETY: every method forwards to `System` with the caller's arguments unchanged and returns its result as is,
+// so the `GlobalAlloc` contract holds as long as it holds for `System`. Counting only touches atomics and never allocates.
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the caller guarantees that `layout` has a non-zero size.
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        // SAFETY: the caller guarantees that `layout` has a non-zero size.
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::on_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        // SAFETY: the caller guarantees that `ptr` was allocated by this allocator, i.e. by `System`, with `layout`.
+        System.dealloc(ptr, layout);
+        Self::on_dealloc(layout.size());
+    }
+
+    // a reallocation counts as a new allocation of `new_size` bytes that replaces the old one.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        // SAFETY: the caller guarantees that `ptr` was allocated by `System` with `layout`,
+        // and that `new_size` is non-zero and doesn't overflow `isize` when rounded up to the alignment.
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::on_dealloc(layout.size());
+            Self::on_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// The allocations made while running a part.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// The total number of bytes allocated.
+    pub bytes: u64,
+    /// The number of allocations, reallocations included.
+    pub count: u64,
+    /// The highest number of bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} in {} allocs, peak {}",
+            format_bytes(self.bytes),
+            self.count,
+            format_bytes(self.peak_bytes)
+        )
+    }
+}
+
+/// Whether allocations are counted, i.e. the `count_allocations` feature is enabled.
+#[must_use]
+pub fn is_enabled() -> bool {
+    cfg!(feature = "count_allocations")
+}
+
+/// Runs `func` and returns the allocations it made, [`None`] if allocations are not counted.
+/// The counters are shared by the whole process, so allocations of other threads are included,
+/// e.g. those of other days when `all` runs them in parallel in-process, which is why it runs them isolated instead.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !is_enabled() {
+        return (func(), None);
+    }
+
+    let bytes = ALLOCATED_BYTES.load(Ordering::Relaxed);
+    let count = ALLOCATIONS.load(Ordering::Relaxed);
+    let live = LIVE_BYTES.load(Ordering::Relaxed);
+    PEAK_LIVE_BYTES.store(live, Ordering::Relaxed);
+
+    let result = func();
+
+    let stats = AllocStats {
+        bytes: ALLOCATED_BYTES.load(Ordering::Relaxed) - bytes,
+        count: ALLOCATIONS.load(Ordering::Relaxed) - count,
+        peak_bytes: PEAK_LIVE_BYTES.load(Ordering::Relaxed).saturating_sub(live),
+    };
+
+    (result, Some(stats))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{measure, AllocStats};
+
+    #[test]
+    fn formats_stats() {
+        let stats = AllocStats {
+            bytes: 3 << 20,
+            count: 42,
+            peak_bytes: 1536,
+        };
+        assert_eq!(stats.to_string(), "3.0 MiB in 42 allocs, peak 1.5 KiB");
+    }
+
+    #[test]
+    #[cfg(feature = "count_allocations")]
+    fn counts_allocations() {
+        let (_, stats) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(16);
+            v.extend(0..16);
+            drop(v);
+            Box::new([0_u8; 64])
+        });
+
+        let stats = stats.unwrap();
+        assert!(stats.count >= 2);
+        assert!(stats.bytes >= 128 + 64);
+        assert!(stats.peak_bytes >= 128);
+    }
+
+    #[test]
+    #[cfg(not(feature = "count_allocations"))]
+    fn skips_measuring_without_feature() {
+        assert_eq!(measure(|| 1), (1, None));
+    }
+}
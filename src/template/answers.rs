@@ -0,0 +1,127 @@
+/// Module that stores the verified answers of each day, so changes to a solution can be checked against them.
+/// Answers live in `data/answers/NN.txt` (`data/YYYY/answers/NN.txt` for other years), one `<part>: <answer>` line per part.
+/// Line breaks of grid answers are stored as `\n`.
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::{fs, io};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::get_data_dir;
+use crate::{Answer, Day, Part, Year};
+
+/// The result of checking an answer against the recorded one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Pass,
+    Fail,
+    /// There is no recorded answer for this part yet.
+    #[default]
+    Unknown,
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => f.write_str("✔"),
+            Verdict::Fail => f.write_str("✘"),
+            Verdict::Unknown => f.write_str("?"),
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path_for_answers(year: Option<Year>, day: Day) -> String {
+    get_data_dir(year, "answers")
+        .join(format!("{day}.txt"))
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn parse_answers(s: &str) -> BTreeMap<Part, String> {
+    s.lines()
+        .filter_map(|l| {
+            let (part, answer) = l.split_once(':')?;
+            let answer = answer.trim().replace("\\n", "\n");
+            Some((part.trim().parse().ok()?, answer))
+        })
+        .collect()
+}
+
+fn serialize_answers(answers: &BTreeMap<Part, String>) -> String {
+    answers
+        .iter()
+        .map(|(part, answer)| format!("{part}: {}\n", answer.replace('\n', "\\n")))
+        .collect()
+}
+
+/// Reads the recorded answer for a part, if there is one.
+#[must_use]
+pub fn expected(year: Option<Year>, day: Day, part: Part) -> Option<String> {
+    let s = fs::read_to_string(get_path_for_answers(year, day)).ok()?;
+    parse_answers(&s).remove(&part)
+}
+
+/// Checks an answer against the expected one.
+#[must_use]
+pub fn verify(answer: Option<&Answer>, expected: Option<&str>) -> Verdict {
+    match (answer, expected) {
+        (_, None) => Verdict::Unknown,
+        (Some(answer), Some(expected)) if answer.to_string().trim() == expected => Verdict::Pass,
+        _ => Verdict::Fail,
+    }
+}
+
+/// Saves the answer of a part, keeping recorded answers of the other part intact.
+pub fn record(year: Option<Year>, day: Day, part: Part, answer: &Answer) -> Result<(), io::Error> {
+    let path = get_path_for_answers(year, day);
+
+    let mut answers = match fs::read_to_string(&path) {
+        Ok(s) => parse_answers(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+        Err(e) => return Err(e),
+    };
+
+    answers.insert(part, answer.to_string().trim().to_string());
+
+    fs::create_dir_all(get_data_dir(year, "answers"))?;
+    fs::write(path, serialize_answers(&answers))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_answers, serialize_answers, verify, Verdict};
+    use crate::{part, Answer};
+
+    #[test]
+    fn parses_answers() {
+        let answers = parse_answers("1: 142\n2:  281 \n\nnot an answer\n3: 1\n");
+        assert_eq!(answers.get(&part!(1)).unwrap(), "142");
+        assert_eq!(answers.get(&part!(2)).unwrap(), "281");
+        assert_eq!(answers.len(), 2);
+    }
+
+    #[test]
+    fn serializes_answers() {
+        let answers = parse_answers("2: 281\n1: 142");
+        assert_eq!(serialize_answers(&answers), "1: 142\n2: 281\n");
+    }
+
+    #[test]
+    fn escapes_grid_answers() {
+        let answers = parse_answers("1: #.\\n.#");
+        assert_eq!(answers.get(&part!(1)).unwrap(), "#.\n.#");
+        assert_eq!(serialize_answers(&answers), "1: #.\\n.#\n");
+    }
+
+    #[test]
+    fn verifies_answers() {
+        let answer = Answer::from(142_u32);
+        assert_eq!(verify(Some(&answer), Some("142")), Verdict::Pass);
+        assert_eq!(verify(Some(&answer), Some("143")), Verdict::Fail);
+        assert_eq!(verify(None, Some("142")), Verdict::Fail);
+        assert_eq!(verify(Some(&answer), None), Verdict::Unknown);
+        assert_eq!(verify(None, None), Verdict::Unknown);
+    }
+}
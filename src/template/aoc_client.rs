@@ -0,0 +1,465 @@
+/// Module that talks to the advent of code website.
+/// Requests are authenticated with the session cookie of a logged in user.
+/// The base URL can be changed via `AOC_BASE_URL`, e.g. to point the client at a local mock server.
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::time::Duration;
+use std::{env, fs, io};
+
+use regex::Regex;
+
+use crate::template::{default_year, get_data_dir};
+use crate::{Answer, Day, Part, Year};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+static SESSION_FILE: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum Error {
+    /// Neither `AOC_SESSION` nor the session file are set.
+    MissingSession,
+    /// Neither `--year` nor `AOC_YEAR` are set.
+    MissingYear,
+    /// The server responded with an error status, e.g. because the puzzle is not unlocked yet.
+    Status(u16),
+    /// The request did not reach the server.
+    Transport(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<ureq::Error> for Error {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => Error::Status(status),
+            ureq::Error::Transport(e) => Error::Transport(e.to_string()),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create a \"~/{SESSION_FILE}\" file."
+            ),
+            Error::MissingYear => write!(f, "no year set. Pass `--year` or set `AOC_YEAR`."),
+            Error::Status(400 | 500) => write!(
+                f,
+                "the server rejected the request, the session cookie might have expired."
+            ),
+            Error::Status(404) => {
+                write!(f, "the puzzle was not found, it might not be unlocked yet.")
+            }
+            Error::Status(status) => write!(f, "the server responded with status {status}."),
+            Error::Transport(e) => write!(f, "could not reach the server: {e}"),
+            Error::IO(e) => write!(f, "could not write output files to file system: {e}"),
+        }
+    }
+}
+
+/// The server's verdict on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Submission {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, the duration is the time left to wait if the server reported it.
+    RateLimited(Option<Duration>),
+    /// The part was already solved, or part one has to be solved first.
+    WrongLevel,
+    /// The response did not contain any of the known messages.
+    Unknown,
+}
+
+impl Display for Submission {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Submission::Correct => write!(f, "That's the right answer! ⭐️"),
+            Submission::Wrong => write!(f, "That's not the right answer."),
+            Submission::TooHigh => {
+                write!(f, "That's not the right answer, your answer is too high.")
+            }
+            Submission::TooLow => write!(f, "That's not the right answer, your answer is too low."),
+            Submission::RateLimited(Some(wait)) => write!(
+                f,
+                "You gave an answer too recently, wait {}s before trying again.",
+                wait.as_secs()
+            ),
+            Submission::RateLimited(None) => {
+                write!(
+                    f,
+                    "You gave an answer too recently, wait a bit before trying again."
+                )
+            }
+            Submission::WrongLevel => write!(
+                f,
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            Submission::Unknown => write!(f, "Could not make sense of the server's response."),
+        }
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl Client {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Creates a client from `AOC_BASE_URL` and the session in `AOC_SESSION` or `~/.adventofcode.session`.
+    pub fn from_env() -> Result<Self, Error> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = get_session().ok_or(Error::MissingSession)?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetches the puzzle input of a day.
+    pub fn input(&self, year: Option<Year>, day: Day) -> Result<String, Error> {
+        let url = format!("{}/input", self.day_url(year, day)?);
+        self.get(&url)
+    }
+
+    /// Fetches the description of a day and converts it to markdown.
+    pub fn puzzle(&self, year: Option<Year>, day: Day) -> Result<String, Error> {
+        let url = self.day_url(year, day)?;
+        Ok(puzzle_to_markdown(&self.get(&url)?))
+    }
+
+    pub fn submit(
+        &self,
+        year: Option<Year>,
+        day: Day,
+        part: Part,
+        answer: &Answer,
+    ) -> Result<Submission, Error> {
+        let url = format!("{}/answer", self.day_url(year, day)?);
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", answer.to_string().trim()),
+            ])?;
+
+        Ok(parse_submission(&response.into_string()?))
+    }
+
+    fn day_url(&self, year: Option<Year>, day: Day) -> Result<String, Error> {
+        let year = year.or_else(default_year).ok_or(Error::MissingYear)?;
+        Ok(format!("{}/{year}/day/{}", self.base_url, day.into_inner()))
+    }
+
+    fn get(&self, url: &str) -> Result<String, Error> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()?;
+
+        Ok(response.into_string()?)
+    }
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    let session = fs::read_to_string(std::path::Path::new(&home).join(SESSION_FILE)).ok()?;
+    Some(session.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+#[must_use]
+pub fn get_input_path(year: Option<Year>, day: Day) -> String {
+    let path = get_data_dir(year, "inputs").join(format!("{day}.txt"));
+    path.to_string_lossy().into_owned()
+}
+
+#[must_use]
+pub fn get_puzzle_path(year: Option<Year>, day: Day) -> String {
+    let path = get_data_dir(year, "puzzles").join(format!("{day}.md"));
+    path.to_string_lossy().into_owned()
+}
+
+/// Writes a file, creating its directory first.
+/// Years that were not worked on yet usually don't have their data directories yet.
+pub fn write_file(path: &str, contents: &str) -> Result<(), Error> {
+    if let Some(dir) = std::path::Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+fn regex(cell: &'static OnceLock<Regex>, pattern: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(pattern).unwrap())
+}
+
+fn parse_submission(html: &str) -> Submission {
+    static WAIT: OnceLock<Regex> = OnceLock::new();
+
+    let text = match html.split_once("<article") {
+        Some((_, article)) => article,
+        None => html,
+    };
+
+    if text.contains("That's the right answer") {
+        Submission::Correct
+    } else if text.contains("You gave an answer too recently") {
+        let wait = regex(&WAIT, r"(?:(\d+)m )?(\d+)s left to wait")
+            .captures(text)
+            .map(|c| {
+                let minutes = c.get(1).map_or(0, |m| m.as_str().parse().unwrap_or(0));
+                let seconds: u64 = c[2].parse().unwrap_or(0);
+                Duration::from_secs(minutes * 60 + seconds)
+            });
+        Submission::RateLimited(wait)
+    } else if text.contains("your answer is too high") {
+        Submission::TooHigh
+    } else if text.contains("your answer is too low") {
+        Submission::TooLow
+    } else if text.contains("That's not the right answer") {
+        Submission::Wrong
+    } else if text.contains("You don't seem to be solving the right level") {
+        Submission::WrongLevel
+    } else {
+        Submission::Unknown
+    }
+}
+
+/// Converts the puzzle articles of a day's page to markdown.
+/// Only the handful of tags used on puzzle pages are converted, other tags are dropped.
+fn puzzle_to_markdown(html: &str) -> String {
+    static ARTICLE: OnceLock<Regex> = OnceLock::new();
+    static PRE: OnceLock<Regex> = OnceLock::new();
+    static BLANK_LINES: OnceLock<Regex> = OnceLock::new();
+
+    let articles = regex(&ARTICLE, r"(?s)<article[^>]*>(.*?)</article>")
+        .captures_iter(html)
+        .map(|c| c[1].to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let mut markdown = String::new();
+    let mut last = 0;
+
+    // code blocks are kept verbatim, apart from their tags.
+    for c in regex(&PRE, r"(?s)<pre><code>(.*?)</code></pre>").captures_iter(&articles) {
+        let m = c.get(0).unwrap();
+        markdown.push_str(&inline_to_markdown(&articles[last..m.start()]));
+        markdown.push_str(&format!(
+            "\n```\n{}```\n\n",
+            decode_entities(&strip_tags(&c[1]))
+        ));
+        last = m.end();
+    }
+
+    markdown.push_str(&inline_to_markdown(&articles[last..]));
+
+    let markdown = regex(&BLANK_LINES, r"\n{3,}").replace_all(&markdown, "\n\n");
+    format!("{}\n", markdown.trim())
+}
+
+fn inline_to_markdown(html: &str) -> String {
+    static RULES: OnceLock<Vec<(Regex, &str)>> = OnceLock::new();
+
+    let rules = RULES.get_or_init(|| {
+        [
+            (r"(?s)<h2[^>]*>(.*?)</h2>", "\n## $1\n\n"),
+            (r"(?s)<code>(.*?)</code>", "`$1`"),
+            (r"(?s)<em[^>]*>(.*?)</em>", "*$1*"),
+            (r#"(?s)<a href="([^"]*)"[^>]*>(.*?)</a>"#, "[$2]($1)"),
+            (r"<li>", "- "),
+            (r"</li>\n?", "\n"),
+            (r"</?ul>\n?", "\n"),
+            (r"</p>", "\n\n"),
+        ]
+        .into_iter()
+        .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), replacement))
+        .collect()
+    });
+
+    let markdown = rules.iter().fold(html.to_string(), |s, (re, replacement)| {
+        re.replace_all(&s, *replacement).into_owned()
+    });
+
+    decode_entities(&strip_tags(&markdown))
+}
+
+fn strip_tags(html: &str) -> String {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    regex(&TAG, r"<[^>]*>").replace_all(html, "").into_owned()
+}
+
+fn decode_entities(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_submission, puzzle_to_markdown, Client, Error, Submission};
+    use crate::{day, part, year};
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    /// Serves a single request with the given status and body, and returns the raw request.
+    fn mock_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut payload = vec![0; content_length];
+            reader.read_exact(&mut payload).unwrap();
+            request.push_str(&String::from_utf8(payload).unwrap());
+
+            write!(
+                stream,
+                "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            tx.send(request).unwrap();
+        });
+
+        (base_url, rx)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (base_url, rx) = mock_server(200, "1\n2\n3\n");
+        let client = Client::new(&base_url, "abc");
+
+        let input = client.input(Some(year!(2022)), day!(1)).unwrap();
+        assert_eq!(input, "1\n2\n3\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("GET /2022/day/1/input "));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, rx) = mock_server(
+            200,
+            "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>",
+        );
+        let client = Client::new(&base_url, "abc");
+
+        let submission = client.submit(Some(year!(2022)), day!(9), part!(2), &42.into());
+        assert_eq!(submission.unwrap(), Submission::TooLow);
+
+        let request = rx.recv().unwrap();
+        assert!(request.starts_with("POST /2022/day/9/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn surfaces_error_status() {
+        let (base_url, _rx) = mock_server(404, "Not Found");
+        let client = Client::new(&base_url, "abc");
+
+        let res = client.puzzle(Some(year!(2022)), day!(25));
+        assert!(matches!(res, Err(Error::Status(404))));
+    }
+
+    #[test]
+    fn parses_submission_responses() {
+        let cases = [
+            ("<article><p>That's the right answer!</p></article>", Submission::Correct),
+            ("<article><p>That's not the right answer.</p></article>", Submission::Wrong),
+            ("<article><p>That's not the right answer; your answer is too high.</p></article>", Submission::TooHigh),
+            ("<article><p>You don't seem to be solving the right level.</p></article>", Submission::WrongLevel),
+            ("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>", Submission::RateLimited(Some(Duration::from_secs(65)))),
+            ("<article><p>You gave an answer too recently. You have 12s left to wait.</p></article>", Submission::RateLimited(Some(Duration::from_secs(12)))),
+            ("<html></html>", Submission::Unknown),
+        ];
+
+        for (html, expected) in cases {
+            assert_eq!(parse_submission(html), expected, "{html}");
+        }
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let html = r#"<html><main><article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is <em>wrong</em> with <a href="/2023/about" target="_blank">global snow</a> production.</p>
+<pre><code>1abc2
+pqr3stu8vwx &lt;<em>x</em>&gt;
+</code></pre>
+<p>What is the sum of all of the <code>calibration values</code>?</p>
+<ul>
+<li>One</li>
+<li>Two</li>
+</ul>
+</article><p>Your puzzle answer was <code>142</code>.</p></main></html>"#;
+
+        assert_eq!(
+            puzzle_to_markdown(html),
+            [
+                "## --- Day 1: Trebuchet?! ---",
+                "",
+                "Something is *wrong* with [global snow](/2023/about) production.",
+                "",
+                "```",
+                "1abc2",
+                "pqr3stu8vwx <x>",
+                "```",
+                "",
+                "What is the sum of all of the `calibration values`?",
+                "",
+                "- One",
+                "- Two",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+}
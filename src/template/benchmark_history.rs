@@ -0,0 +1,248 @@
+/// Module that persists benchmark runs so they can be compared against each other.
+/// Every run is appended to a JSON lines file, one run per line.
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::runner::Record;
+use crate::template::scoped_year;
+use crate::{Day, Part, Year};
+
+static HISTORY_PATH: &str = "data/benchmarks.jsonl";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse benchmark history: {e}"),
+            Error::IO(e) => write!(f, "could not access benchmark history: {e}"),
+        }
+    }
+}
+
+/// A single `cargo time` invocation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Run {
+    /// Abbreviated hash of the commit that was benched, suffixed with `-dirty` if the work tree had changes.
+    pub commit: String,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub records: Vec<Record>,
+}
+
+impl Run {
+    #[must_use]
+    pub fn new(records: Vec<Record>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            commit: get_commit().unwrap_or_else(|| "unknown".into()),
+            timestamp,
+            records,
+        }
+    }
+
+    fn duration_nanos(&self, year: Option<Year>, day: Day, part: Part) -> Option<u64> {
+        self.records
+            .iter()
+            .find(|r| {
+                scoped_year(r.year) == scoped_year(year)
+                    && r.day == day
+                    && r.part == part
+                    && r.success
+            })
+            .map(|r| r.duration_nanos)
+    }
+}
+
+/// The change of a part's median execution time between two runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub year: Option<Year>,
+    pub day: Day,
+    pub part: Part,
+    pub baseline_nanos: u64,
+    pub latest_nanos: u64,
+    /// Relative change in percent, positive values mean the part got slower.
+    pub change: f64,
+    pub is_regression: bool,
+}
+
+fn get_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let mut commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain"])
+        .output()
+        .is_ok_and(|o| !o.stdout.is_empty());
+
+    if is_dirty {
+        commit.push_str("-dirty");
+    }
+
+    Some(commit)
+}
+
+fn parse_history(s: &str) -> Result<Vec<Run>, Error> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).map_err(|e| Error::Parser(e.to_string())))
+        .collect()
+}
+
+/// Compares every part that was solved in both runs.
+/// Parts that got slower by more than `threshold` percent are marked as regressions.
+#[must_use]
+pub fn compare(baseline: &Run, latest: &Run, threshold: f64) -> Vec<Comparison> {
+    latest
+        .records
+        .iter()
+        .filter(|r| r.success)
+        .filter_map(|r| {
+            let baseline_nanos = baseline.duration_nanos(r.year, r.day, r.part)?;
+            let latest_nanos = r.duration_nanos;
+
+            #[allow(clippy::cast_precision_loss)]
+            let change = if baseline_nanos == 0 {
+                0_f64
+            } else {
+                (latest_nanos as f64 / baseline_nanos as f64 - 1_f64) * 100_f64
+            };
+
+            Some(Comparison {
+                year: r.year,
+                day: r.day,
+                part: r.part,
+                baseline_nanos,
+                latest_nanos,
+                change,
+                is_regression: change > threshold,
+            })
+        })
+        .collect()
+}
+
+pub fn load() -> Result<Vec<Run>, Error> {
+    match fs::read_to_string(HISTORY_PATH) {
+        Ok(s) => parse_history(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn append(run: &Run) -> Result<(), Error> {
+    let line = serde_json::to_string(run).map_err(|e| Error::Parser(e.to_string()))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_PATH)?;
+
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, parse_history, Run};
+    use crate::template::runner::Record;
+    use crate::{day, year, Day, Part};
+
+    fn record(day: u8, part: u8, duration_nanos: u64) -> Record {
+        Record {
+            success: true,
+            answer: Some("42".into()),
+            duration_nanos,
+            min_nanos: duration_nanos,
+            p95_nanos: duration_nanos,
+            samples: 10,
+            ..Record::empty(Day::new(day).unwrap(), Part::new(part).unwrap())
+        }
+    }
+
+    fn run(commit: &str, records: Vec<Record>) -> Run {
+        Run {
+            commit: commit.into(),
+            timestamp: 0,
+            records,
+        }
+    }
+
+    #[test]
+    fn flags_regressions_above_threshold() {
+        let baseline = run("a", vec![record(1, 1, 100), record(1, 2, 100)]);
+        let latest = run("b", vec![record(1, 1, 105), record(1, 2, 150)]);
+
+        let res = compare(&baseline, &latest, 10.0);
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].is_regression, false);
+        assert_eq!(res[1].is_regression, true);
+        assert_eq!(res[1].day, day!(1));
+        assert_eq!(res[1].change, 50.0);
+    }
+
+    #[test]
+    fn skips_parts_missing_from_baseline() {
+        let baseline = run("a", vec![record(1, 1, 100)]);
+        let latest = run("b", vec![record(1, 1, 50), record(2, 1, 100)]);
+
+        let res = compare(&baseline, &latest, 10.0);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].change, -50.0);
+    }
+
+    #[test]
+    fn matches_parts_of_the_same_year() {
+        let mut other_year = record(1, 1, 100);
+        other_year.year = Some(year!(2015));
+
+        let baseline = run("a", vec![other_year.clone()]);
+        let latest = run("b", vec![record(1, 1, 200), other_year]);
+
+        let res = compare(&baseline, &latest, 10.0);
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].year, Some(year!(2015)));
+        assert_eq!(res[0].change, 0.0);
+    }
+
+    #[test]
+    fn parses_history_lines() {
+        let runs = [run("a", vec![record(1, 1, 100)]), run("b", vec![])];
+        let s = runs
+            .iter()
+            .map(|r| serde_json::to_string(r).unwrap())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let parsed = parse_history(&format!("{s}\n\n")).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].records, runs[0].records);
+        assert_eq!(parsed[1].commit, "b");
+    }
+}
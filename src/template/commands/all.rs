@@ -1,109 +1,414 @@
-use std::io;
+use std::collections::HashMap;
+
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
 use crate::template::{
+    allocator,
+    benchmark_history::{self, Run},
+    commands::Error,
+    default_year, get_bin_name, get_years,
+    limits::Limits,
     readme_benchmarks::{self, Timings},
-    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+    registry::Solution,
+    runner::{print_parse, print_peak_rss, print_record, record_answer, OutputFormat, Record},
+    scoped_year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{all_days, Day, Part, Year};
+
+/// Runs every solution of a year, or of every year present if `is_all_years` is set.
+/// If a part is given, only that part of each solution runs.
+/// Solutions run either in-process via the `solutions` registry or as isolated binaries.
+/// The registry is empty unless this binary was built with the `in_process` feature, in which case `is_isolated` is implied.
+/// Solutions in the registry are built with the same profile as this binary, so `is_isolated` is implied as well if
+/// that profile doesn't match `is_release`.
+/// Limits can only be enforced on isolated binaries, so setting any of them implies `is_isolated`, too.
+/// The same goes for counting allocations if this binary was built without the counting allocator, or if days run in parallel,
+/// since the allocation counters are shared by the whole process.
+/// Days only run in parallel if they are not timed, fails with [`Error::Usage`] otherwise.
+///
+/// Days that fail don't stop the run, they are reported once all days ran: the command fails with
+/// [`Error::Build`] if any solution did not compile, [`Error::Crashed`] if any day crashed,
+/// [`Error::Killed`] if any day exceeded a limit and [`Error::Usage`] if the input of any day is missing.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Option<Year>,
+    is_all_years: bool,
+    is_release: bool,
+    is_timed: bool,
+    format: OutputFormat,
+    is_isolated: bool,
+    is_parallel: bool,
+    is_recording: bool,
+    part: Option<Part>,
+    limits: Limits,
+    count_allocations: bool,
+    solutions: &[&dyn Solution],
+) -> Result<(), Error> {
+    if is_parallel && is_timed {
+        return Err(Error::Usage(
+            "`--parallel` can't be combined with `--time`, days that run at the same time compete for CPU time and skew the benchmarks.".into(),
+        ));
+    }
+
+    let is_isolated = is_isolated
+        || solutions.is_empty()
+        || is_release == cfg!(debug_assertions)
+        || !limits.is_empty()
+        || (count_allocations && (is_parallel || !allocator::is_enabled()));
+
+    let years: Vec<_> = if is_all_years {
+        get_years().into_iter().map(scoped_year).collect()
+    } else {
+        vec![scoped_year(year)]
+    };
 
-pub fn handle(is_release: bool, is_timed: bool) {
+    // timings of other years are not part of the readme table.
     let mut timings: Vec<Timings> = vec![];
+    let mut total_nanos = 0_f64;
+    let mut all_records = vec![];
+    let mut failed_builds = vec![];
+    let mut crashed = vec![];
+    let mut killed = vec![];
+    let mut missing_inputs = vec![];
+    let is_human = format == OutputFormat::Human;
+
+    // when running in parallel, solve everything upfront and print the results in order below.
+    let mut precomputed: HashMap<(Option<Year>, Day), _> = if is_parallel && !is_isolated {
+        solutions
+            .par_iter()
+            .filter(|solution| years.contains(&scoped_year(solution.year())))
+            .map(|solution| {
+                (
+                    (scoped_year(solution.year()), solution.day()),
+                    in_process::run_solution(*solution, is_timed, part, false),
+                )
+            })
+            .collect()
+    } else {
+        HashMap::new()
+    };
 
-    all_days().for_each(|day| {
-        if day > 1 {
+    for (i, &year) in years.iter().enumerate() {
+        if is_human && is_all_years {
+            if i > 0 {
+                println!();
+            }
+
+            let year = year.or_else(default_year);
+            println!(
+                "{ANSI_BOLD}Year {}{ANSI_RESET}",
+                year.map_or("?".into(), |y| y.to_string())
+            );
+            println!("=========");
             println!();
         }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+        for day in all_days() {
+            if is_human {
+                if day > 1 {
+                    println!();
+                }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+                println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+                println!("------");
+            }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            let DayRun { records, failure } = if is_isolated {
+                child_commands::run_solution(
+                    year,
+                    day,
+                    is_timed,
+                    is_release,
+                    format,
+                    part,
+                    limits,
+                    count_allocations,
+                )?
+            } else {
+                let run = precomputed.remove(&(year, day)).unwrap_or_else(|| {
+                    solutions
+                        .iter()
+                        .find(|solution| {
+                            scoped_year(solution.year()) == year && solution.day() == day
+                        })
+                        .map(|solution| in_process::run_solution(*solution, is_timed, part, true))
+                        .unwrap_or_default()
+                });
+
+                if is_human {
+                    if let Some(stats) = run.records.first().and_then(Record::parse_stats) {
+                        print_parse(&stats);
+                    }
+                }
+
+                for record in &run.records {
+                    print_record(record, format);
+                }
+
+                run
+            };
+
+            if is_human {
+                if let Some(bytes) = records.iter().filter_map(|r| r.peak_rss_bytes).max() {
+                    print_peak_rss(bytes);
+                }
+            }
+
+            let bin_name = get_bin_name(year, day);
+
+            if let Some(limit) = records.iter().find_map(|r| r.limit) {
+                killed.push(format!("{bin_name} ({limit})"));
+            }
+
+            match failure {
+                Some(Failure::MissingInput) => {
+                    if is_human {
+                        println!("Missing input.");
+                    }
+
+                    missing_inputs.push(bin_name);
+                }
+                Some(Failure::Build) => {
+                    if is_human {
+                        println!("Failed to build.");
+                    }
+
+                    failed_builds.push(bin_name);
+                }
+                Some(Failure::Crashed(crash)) => {
+                    if is_human {
+                        println!("Crashed: {crash}.");
+                    }
+
+                    crashed.push(format!("{bin_name} ({crash})"));
+                }
+                None if is_human && records.is_empty() => println!("Not solved."),
+                None => {}
+            }
+
+            if !records.is_empty() {
+                if is_recording {
+                    records.iter().for_each(record_answer);
+                }
+
+                let day_timings = child_commands::collect_timings(&records, day);
+                total_nanos += day_timings.total_nanos;
+
+                if year.is_none() {
+                    timings.push(day_timings);
+                }
+
+                all_records.extend(records);
+            }
         }
-    });
+    }
 
     if is_timed {
-        let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+        let total_millis = total_nanos / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if is_human {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
 
         if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
+            // the readme only lists both parts of solutions of the default year.
+            if let Some(part) = part {
+                eprintln!("Skipped updating README with benchmarks, only part {part} was run.");
+            } else if years.contains(&None) {
+                let readme_millis =
+                    timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
+
+                match readme_benchmarks::update(timings, readme_millis) {
+                    Ok(()) => eprintln!("Successfully updated README with benchmarks."),
+                    Err(_) => {
+                        eprintln!("Failed to update readme with benchmarks.");
+                    }
                 }
             }
+
+            match benchmark_history::append(&Run::new(all_records)) {
+                Ok(()) => eprintln!("Successfully recorded benchmarks in history."),
+                Err(e) => eprintln!("Failed to record benchmarks in history: {e}"),
+            }
         }
     }
+
+    if !failed_builds.is_empty() {
+        return Err(Error::Build(failed_builds));
+    }
+
+    if !crashed.is_empty() {
+        return Err(Error::Crashed(crashed));
+    }
+
+    if !killed.is_empty() {
+        return Err(Error::Killed(killed));
+    }
+
+    if !missing_inputs.is_empty() {
+        return Err(Error::missing_input(&missing_inputs));
+    }
+
+    Ok(())
 }
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
+/// The records of a day, and why its solution failed if it did.
+#[derive(Debug, Default)]
+struct DayRun {
+    records: Vec<Record>,
+    failure: Option<Failure>,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+impl DayRun {
+    fn failed(failure: Failure) -> Self {
+        Self {
+            records: vec![],
+            failure: Some(failure),
+        }
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Why a day produced no records.
+#[derive(Debug)]
+enum Failure {
+    /// The input file of the day does not exist.
+    MissingInput,
+    /// The solution binary did not compile.
+    Build,
+    /// The solution crashed or panicked, with a description of how.
+    Crashed(String),
+}
+
+/// Runs solutions registered with the `solution!` macro inside of this process.
+mod in_process {
+    use super::{DayRun, Failure};
+    use crate::template::{limits, registry::Solution, try_read_file};
+    use crate::Part;
+    use std::panic::{self, AssertUnwindSafe};
+
+    /// Runs a solution, catching panics so that one crashing day does not end the whole run.
+    /// If `is_measuring_memory` is set, the peak memory usage of the process while the day runs is recorded.
+    /// The peak starts from the memory the process already uses, and it can't be attributed to a day when days run in parallel.
+    pub fn run_solution(
+        solution: &dyn Solution,
+        is_timed: bool,
+        part: Option<Part>,
+        is_measuring_memory: bool,
+    ) -> DayRun {
+        let day = solution.day();
+
+        let Ok(input) = try_read_file(solution.year(), "inputs", day) else {
+            return DayRun::failed(Failure::MissingInput);
+        };
+
+        let is_measuring_memory = is_measuring_memory && limits::reset_peak_rss();
+
+        match panic::catch_unwind(AssertUnwindSafe(|| {
+            solution.run_parts(&input, part, is_timed)
+        })) {
+            Ok(mut records) => {
+                if is_measuring_memory {
+                    let peak_rss_bytes = limits::peak_rss(None);
+                    records
+                        .iter_mut()
+                        .for_each(|record| record.peak_rss_bytes = peak_rss_bytes);
+                }
+
+                DayRun {
+                    records,
+                    failure: None,
+                }
+            }
+            Err(_) => DayRun::failed(Failure::Crashed("panicked".into())),
+        }
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the records they emit.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
+    use super::{DayRun, Error, Failure};
+    use crate::template::commands::describe_exit;
+    use crate::template::limits::{self, Limits};
+    use crate::template::runner::{print_parse, print_record, InputSource, OutputFormat, Record};
+    use crate::template::{get_bin_name, get_path_for_bin};
+    use crate::{all_parts, Day, Part, Year};
     use std::{
-        io::{BufRead, BufReader},
+        collections::BTreeMap,
+        io::{self, BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
         thread,
+        time::Duration,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given day, killing it if it exceeds one of the limits.
+    /// Parts that did not finish before that are recorded with the exceeded limit.
+    /// Solutions that exit with an error are reported as crashed, days without an input are not built or run.
+    #[allow(clippy::too_many_arguments)]
+    pub fn run_solution(
+        year: Option<Year>,
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        format: OutputFormat,
+        part: Option<Part>,
+        limits: Limits,
+        count_allocations: bool,
+    ) -> Result<DayRun, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_path_for_bin(year, day)).exists() {
+            return Ok(DayRun::default());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
-
-        if is_release {
-            args.push("--release");
+        if !InputSource::Puzzle
+            .path(year, day)
+            .is_some_and(|path| path.exists())
+        {
+            return Ok(DayRun::failed(Failure::MissingInput));
         }
 
+        // build first, so compile time does not count towards the timeout.
+        let Some(executable) =
+            limits::build(&get_bin_name(year, day), is_release, count_allocations)
+                .map_err(Error::io("build solution"))?
+        else {
+            return Ok(DayRun::failed(Failure::Build));
+        };
+
+        // children always report results as records, they are rendered by this process.
+        let mut args = vec!["--format", "json"];
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        let part_str = part.map(|part| part.to_string());
+        if let Some(part) = &part_str {
+            args.extend(["--part", part]);
+        }
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr while collecting records from stdout.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new(executable)
             .args(&args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .spawn()?;
-
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+            .spawn()
+            .map_err(Error::io("run solution"))?;
+
+        let broken_pipe = || {
+            Error::IO(
+                "read solution output".into(),
+                io::ErrorKind::BrokenPipe.into(),
+            )
+        };
+        let stdout = BufReader::new(cmd.stdout.take().ok_or_else(broken_pipe)?);
+        let stderr = BufReader::new(cmd.stderr.take().ok_or_else(broken_pipe)?);
 
         let mut output = vec![];
 
@@ -113,79 +418,114 @@ mod child_commands {
             });
         });
 
+        let supervisor = thread::spawn(move || limits::supervise(cmd, limits));
+
         for line in stdout.lines() {
             let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+
+            match (parse_record(&line), format) {
+                (Some(record), _) => {
+                    if format == OutputFormat::Human && output.is_empty() {
+                        if let Some(stats) = record.parse_stats() {
+                            print_parse(&stats);
+                        }
+                    }
+
+                    print_record(&record, format);
+                    output.push(record);
+                }
+                // anything else was printed by the solution itself.
+                // keep stdout machine-readable in json mode.
+                (None, OutputFormat::Human) => println!("{line}"),
+                (None, OutputFormat::Json) => eprintln!("{line}"),
+            }
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let outcome = supervisor
+            .join()
+            .unwrap()
+            .map_err(Error::io("run solution"))?;
+
+        if let Some(limit) = outcome.exceeded {
+            let unfinished: Vec<Part> = all_parts()
+                .filter(|p| part.is_none_or(|part| part == *p))
+                .filter(|p| output.iter().all(|record| record.part != *p))
+                .collect();
+
+            for part in unfinished {
+                let record = Record::exceeded(year, day, part, limit, outcome.peak_rss_bytes);
+                print_record(&record, format);
+                output.push(record);
+            }
+        }
 
-        Ok(output)
+        // killed solutions did not crash, their unfinished parts carry the limit instead.
+        let failure = (outcome.exceeded.is_none() && !outcome.status.success())
+            .then(|| Failure::Crashed(describe_exit(outcome.status)));
+
+        Ok(DayRun {
+            records: output,
+            failure,
+        })
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
+    fn parse_record(line: &str) -> Option<Record> {
+        serde_json::from_str(line).ok()
+    }
+
+    pub fn collect_timings(records: &[Record], day: Day) -> super::Timings {
         let mut timings = super::Timings {
             day,
-            part_1: None,
-            part_2: None,
+            parse: None,
+            parts: BTreeMap::new(),
+            exceeded: BTreeMap::new(),
+            peak_rss_bytes: records.iter().filter_map(|r| r.peak_rss_bytes).max(),
+            allocations: None,
             total_nanos: 0_f64,
         };
 
-        output
+        // allocations of a day add up over its parts, except for the peak.
+        for allocations in records.iter().filter_map(|r| r.allocations) {
+            let total = timings.allocations.get_or_insert_with(Default::default);
+            total.bytes += allocations.bytes;
+            total.count += allocations.count;
+            total.peak_bytes = total.peak_bytes.max(allocations.peak_bytes);
+        }
+
+        for record in records {
+            if let Some(limit) = record.limit {
+                timings.exceeded.insert(record.part, limit);
+            }
+        }
+
+        // the parse hook runs once per day, every record carries its time.
+        if let Some(parse_nanos) = records.iter().find_map(|record| record.parse_nanos) {
+            timings.parse = Some(format!("{:.1?}", Duration::from_nanos(parse_nanos)));
+
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timings.total_nanos += parse_nanos as f64;
+            }
+        }
+
+        records
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+            .filter(|record| record.success)
+            .for_each(|record| {
+                let timing_str = format!("{:.1?}", Duration::from_nanos(record.duration_nanos));
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+                timings.parts.insert(record.part, timing_str);
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                #[allow(clippy::cast_precision_loss)]
+                {
+                    timings.total_nanos += record.duration_nanos as f64;
                 }
-
-                timings.total_nanos += nanos;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -202,53 +542,90 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{collect_timings, parse_record};
+        use crate::template::limits::Limit;
 
-        use crate::day;
+        use crate::{day, part};
+
+        fn parse_records(lines: &[&str]) -> Vec<super::Record> {
+            lines.iter().filter_map(|l| parse_record(l)).collect()
+        }
 
         #[test]
         fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            let records = parse_records(&[
+                r#"{"day":1,"part":1,"success":true,"answer":0,"duration_nanos":74,"min_nanos":70,"p95_nanos":80,"stddev_nanos":2,"samples":100000}"#,
+                r#"{"day":1,"part":2,"success":true,"answer":10,"duration_nanos":74130000,"min_nanos":74000000,"p95_nanos":75000000,"stddev_nanos":1000,"samples":99999}"#,
+            ]);
+            let res = collect_timings(&records, day!(1));
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.get(part!(1)).unwrap(), "74.0ns");
+            assert_eq!(res.get(part!(2)).unwrap(), "74.1ms");
         }
 
         #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+        fn test_non_record_lines() {
+            let records = parse_records(&[
+                "Part 1: @ @ @ ( ) ms (2s @ 5 samples)",
+                "{ not a record }",
+                r#"{"day":1,"part":2,"success":true,"answer":"10s","duration_nanos":100000000,"min_nanos":100000000,"p95_nanos":100000000,"stddev_nanos":0,"samples":1}"#,
+                "",
+            ]);
+            assert_eq!(records.len(), 1);
+            let res = collect_timings(&records, day!(1));
+            assert_approx_eq!(res.total_nanos, 100000000_f64);
+            assert_eq!(res.get(part!(1)).is_none(), true);
+            assert_eq!(res.get(part!(2)).unwrap(), "100.0ms");
         }
 
         #[test]
         fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
+            let records = parse_records(&[
+                r#"{"day":1,"part":1,"success":false,"answer":null,"duration_nanos":10,"min_nanos":10,"p95_nanos":10,"stddev_nanos":0,"samples":1}"#,
+                r#"{"day":1,"part":2,"success":false,"answer":null,"duration_nanos":10,"min_nanos":10,"p95_nanos":10,"stddev_nanos":0,"samples":1}"#,
+            ]);
+            let res = collect_timings(&records, day!(1));
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.get(part!(1)).is_none(), true);
+            assert_eq!(res.get(part!(2)).is_none(), true);
+        }
+
+        #[test]
+        fn test_parse_timing() {
+            let records = parse_records(&[
+                r#"{"day":1,"part":1,"success":true,"answer":1,"duration_nanos":10,"min_nanos":10,"p95_nanos":10,"stddev_nanos":0,"samples":1,"parse_nanos":2000}"#,
+                r#"{"day":1,"part":2,"success":true,"answer":2,"duration_nanos":20,"min_nanos":20,"p95_nanos":20,"stddev_nanos":0,"samples":1,"parse_nanos":2000}"#,
+            ]);
+            let res = collect_timings(&records, day!(1));
+            assert_approx_eq!(res.total_nanos, 2030_f64);
+            assert_eq!(res.parse.as_deref(), Some("2.0µs"));
+            assert_eq!(res.get(part!(2)).unwrap(), "20.0ns");
+        }
+
+        #[test]
+        fn test_exceeded_limits() {
+            let records = parse_records(&[
+                r#"{"day":1,"part":1,"success":true,"answer":1,"duration_nanos":10,"min_nanos":10,"p95_nanos":10,"stddev_nanos":0,"samples":1,"peak_rss_bytes":1024}"#,
+                r#"{"day":1,"part":2,"success":false,"answer":null,"duration_nanos":0,"min_nanos":0,"p95_nanos":0,"stddev_nanos":0,"samples":0,"peak_rss_bytes":4096,"limit":"timeout"}"#,
+            ]);
+            let res = collect_timings(&records, day!(1));
+            assert_approx_eq!(res.total_nanos, 10_f64);
+            assert_eq!(res.peak_rss_bytes, Some(4096));
+            assert_eq!(res.get(part!(2)).is_none(), true);
+            assert_eq!(res.exceeded.get(&part!(2)), Some(&Limit::Timeout));
+        }
+
+        #[test]
+        fn test_allocations() {
+            let records = parse_records(&[
+                r#"{"day":1,"part":1,"success":true,"answer":1,"duration_nanos":10,"min_nanos":10,"p95_nanos":10,"stddev_nanos":0,"samples":1,"allocations":{"bytes":100,"count":2,"peak_bytes":80}}"#,
+                r#"{"day":1,"part":2,"success":true,"answer":2,"duration_nanos":10,"min_nanos":10,"p95_nanos":10,"stddev_nanos":0,"samples":1,"allocations":{"bytes":50,"count":3,"peak_bytes":50}}"#,
+            ]);
+            let res = collect_timings(&records, day!(1));
+            let allocations = res.allocations.unwrap();
+            assert_eq!(allocations.bytes, 150);
+            assert_eq!(allocations.count, 5);
+            assert_eq!(allocations.peak_bytes, 80);
         }
     }
 }
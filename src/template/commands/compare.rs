@@ -0,0 +1,75 @@
+use std::time::Duration;
+
+use crate::template::benchmark_history::{self, Comparison};
+use crate::template::commands::Error;
+use crate::template::{scoped_year, ANSI_BOLD, ANSI_RESET};
+use crate::Year;
+
+/// Compares the latest benchmark run to a baseline, optionally limited to the solutions of one year.
+/// Fails with [`Error::Regression`] if any part got slower by more than `threshold` percent.
+pub fn handle(year: Option<Year>, baseline: Option<String>, threshold: f64) -> Result<(), Error> {
+    let history = benchmark_history::load()?;
+
+    let Some((latest, previous)) = history.split_last() else {
+        return Err(Error::Usage(
+            "No benchmarks recorded yet. Run `cargo time` to record a run.".into(),
+        ));
+    };
+
+    let baseline_run = match &baseline {
+        Some(commit) => previous.iter().rev().find(|r| r.commit.starts_with(commit)),
+        None => previous.last(),
+    };
+
+    let Some(baseline_run) = baseline_run else {
+        return Err(Error::Usage(match baseline {
+            Some(commit) => format!("No benchmarks recorded for commit \"{commit}\"."),
+            None => "Only one run recorded, there is nothing to compare against yet.".into(),
+        }));
+    };
+
+    println!(
+        "Comparing {} (baseline) to {} (latest), threshold {threshold}%",
+        baseline_run.commit, latest.commit
+    );
+    println!("---");
+
+    let comparisons: Vec<_> = benchmark_history::compare(baseline_run, latest, threshold)
+        .into_iter()
+        .filter(|c| year.is_none() || scoped_year(c.year) == scoped_year(year))
+        .collect();
+
+    for comparison in &comparisons {
+        print_comparison(comparison);
+    }
+
+    let regressions = comparisons.iter().filter(|c| c.is_regression).count();
+
+    if regressions > 0 {
+        eprintln!("---");
+        return Err(Error::Regression(regressions, threshold));
+    }
+
+    Ok(())
+}
+
+fn print_comparison(c: &Comparison) {
+    let year = scoped_year(c.year)
+        .map(|y| format!("{y} "))
+        .unwrap_or_default();
+
+    let line = format!(
+        "{year}Day {} Part {}: {:.1?} -> {:.1?} ({:+.1}%)",
+        c.day,
+        c.part,
+        Duration::from_nanos(c.baseline_nanos),
+        Duration::from_nanos(c.latest_nanos),
+        c.change
+    );
+
+    if c.is_regression {
+        println!("{ANSI_BOLD}{line} ✖{ANSI_RESET}");
+    } else {
+        println!("{line}");
+    }
+}
@@ -1,15 +1,22 @@
-use crate::template::aoc_cli;
-use crate::Day;
-use std::process;
-
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+use crate::template::aoc_client::{self, get_input_path, get_puzzle_path, Client};
+use crate::template::commands::Error;
+use crate::{Day, Year};
+
+pub fn handle(year: Option<Year>, day: Day) -> Result<(), Error> {
+    download(year, day).map_err(Error::Client)
+}
+
+fn download(year: Option<Year>, day: Day) -> Result<(), aoc_client::Error> {
+    let client = Client::from_env()?;
+
+    let input_path = get_input_path(year, day);
+    let puzzle_path = get_puzzle_path(year, day);
+
+    aoc_client::write_file(&input_path, &client.input(year, day)?)?;
+    aoc_client::write_file(&puzzle_path, &client.puzzle(year, day)?)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
 }
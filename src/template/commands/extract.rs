@@ -0,0 +1,71 @@
+use std::fs;
+
+use crate::template::commands::Error;
+use crate::template::puzzle::{self, Example};
+use crate::template::{get_data_dir, scoped_year};
+use crate::{all_parts, Day, Part, Year};
+
+/// Extracts the examples of a day from its downloaded puzzle description.
+/// This is useful after part one was solved and the description of part two was downloaded.
+pub fn handle(year: Option<Year>, day: Day) -> Result<(), Error> {
+    let Some(markdown) = puzzle::read(year, day) else {
+        return Err(Error::Usage(match scoped_year(year) {
+            Some(year) => format!(
+                "No puzzle description found. Run `cargo download {day} --year {year}` first."
+            ),
+            None => format!("No puzzle description found. Run `cargo download {day}` first."),
+        }));
+    };
+
+    let examples = puzzle::examples(&markdown);
+    write_examples(year, day, &examples)?;
+
+    println!("---");
+    for (part, example) in all_parts().zip(&examples) {
+        match &example.answer {
+            Some(answer) => println!("🎄 Part {part} example answer: {answer}"),
+            None => println!("🎄 Part {part}: no example answer found."),
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the example file of a part, `NN.txt` for part one and `NN-2.txt` for part two.
+#[must_use]
+pub fn get_example_path(year: Option<Year>, day: Day, part: Part) -> String {
+    let file_name = match part.into_inner() {
+        1 => format!("{day}.txt"),
+        part => format!("{day}-{part}.txt"),
+    };
+
+    get_data_dir(year, "examples")
+        .join(file_name)
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Writes the example inputs to their files.
+/// Example files that already have contents are left untouched.
+pub fn write_examples(year: Option<Year>, day: Day, examples: &[Example]) -> Result<(), Error> {
+    fs::create_dir_all(get_data_dir(year, "examples"))
+        .map_err(Error::io("create examples directory"))?;
+
+    for (part, example) in all_parts().zip(examples) {
+        let Some(input) = &example.input else {
+            continue;
+        };
+
+        let path = get_example_path(year, day, part);
+
+        if fs::read_to_string(&path).is_ok_and(|s| !s.trim().is_empty()) {
+            println!("Skipped example file \"{path}\", it is not empty.");
+            continue;
+        }
+
+        fs::write(&path, input).map_err(Error::io("write example file"))?;
+        println!("Created example file \"{path}\" from the puzzle description");
+    }
+
+    Ok(())
+}
@@ -1,5 +1,112 @@
+use std::fmt::Display;
+use std::io;
+use std::process::ExitStatus;
+
+use crate::template::{aoc_client, benchmark_history};
+
 pub mod all;
+pub mod compare;
 pub mod download;
+pub mod extract;
+pub mod profile;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod watch;
+
+/// The error a command failed with. Every kind of error exits with its own code, see [`Error::exit_code`].
+#[derive(Debug)]
+pub enum Error {
+    /// The command can't run until the user does something first, e.g. download a puzzle.
+    Usage(String),
+    /// Talking to the advent of code website failed.
+    Client(aoc_client::Error),
+    /// Reading or writing a file failed, the message describes what was attempted.
+    IO(String, io::Error),
+    /// The benchmark history could not be read.
+    History(benchmark_history::Error),
+    /// Solution binaries that did not compile.
+    Build(Vec<String>),
+    /// Solutions that crashed or panicked, with a description of how.
+    Crashed(Vec<String>),
+    /// A solution binary that exited with an error it reported itself, e.g. invalid arguments, with its exit code.
+    Exited(String, u8),
+    /// Solutions that were killed because they exceeded a time or memory limit.
+    Killed(Vec<String>),
+    /// The number of parts that got slower by more than the threshold, in percent.
+    Regression(usize, f64),
+    /// The profiler is missing or failed to profile a solution.
+    Profiler(String),
+}
+
+impl Error {
+    /// Wraps an IO error with what was attempted, e.g. `create module file`.
+    pub fn io(action: impl Into<String>) -> impl FnOnce(io::Error) -> Self {
+        move |e| Error::IO(action.into(), e)
+    }
+
+    /// The days whose puzzle input was not downloaded yet, by bin name.
+    pub fn missing_input(bin_names: &[String]) -> Self {
+        Error::Usage(format!(
+            "missing input: {}. Run `cargo download <day>` first.",
+            bin_names.join(", ")
+        ))
+    }
+
+    /// The code the process exits with. Invalid command-line arguments exit with `2` as well.
+    #[must_use]
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::Client(_) => 3,
+            Error::IO(..) => 4,
+            Error::History(_) => 5,
+            Error::Build(_) => 6,
+            Error::Crashed(_) => 7,
+            Error::Exited(_, code) => *code,
+            Error::Killed(_) => 8,
+            Error::Regression(..) => 9,
+            Error::Profiler(_) => 10,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(message) | Error::Profiler(message) => f.write_str(message),
+            Error::Client(e) => write!(f, "{e}"),
+            Error::IO(action, e) => write!(f, "failed to {action}: {e}"),
+            Error::History(e) => write!(f, "{e}"),
+            Error::Build(bin_names) => write!(f, "failed to build: {}", bin_names.join(", ")),
+            Error::Crashed(days) => write!(f, "crashed: {}", days.join(", ")),
+            Error::Exited(bin_name, code) => write!(f, "{bin_name} exited with code {code}"),
+            Error::Killed(days) => write!(f, "exceeded a limit: {}", days.join(", ")),
+            Error::Regression(count, threshold) => {
+                write!(f, "{count} part(s) got slower by more than {threshold}%")
+            }
+        }
+    }
+}
+
+impl From<aoc_client::Error> for Error {
+    fn from(e: aoc_client::Error) -> Self {
+        Error::Client(e)
+    }
+}
+
+impl From<benchmark_history::Error> for Error {
+    fn from(e: benchmark_history::Error) -> Self {
+        Error::History(e)
+    }
+}
+
+/// Describes how a solution process ended, e.g. `panicked` or `exited with exit status: 1`.
+#[must_use]
+pub fn describe_exit(status: ExitStatus) -> String {
+    // the rust runtime exits with 101 when the main thread panics.
+    match status.code() {
+        Some(101) => "panicked".into(),
+        _ => format!("exited with {status}"),
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::process::{Command, Stdio};
+use std::{env, fs, iter};
+
+use crate::template::commands::{describe_exit, Error};
+use crate::template::runner::InputSource;
+use crate::template::{get_bin_name, get_data_dir, limits};
+use crate::{Day, Part, Year};
+
+/// The cargo profile solutions are profiled with, an optimized build with debug info. See `Cargo.toml`.
+const CARGO_PROFILE: &str = "profiling";
+
+/// How often `perf` samples the solution, in Hz. An odd frequency avoids sampling in lockstep with periodic work.
+const SAMPLE_FREQUENCY: &str = "999";
+
+/// Runs a part of a day in a loop under `perf` and writes its folded stacks to `data/profiles/NN-P.folded`.
+/// The folded stacks can be rendered as a flamegraph, e.g. with `inferno-flamegraph` or `flamegraph.pl`.
+/// Fails with [`Error::Profiler`] if `perf` is missing or could not profile the solution.
+pub fn handle(year: Option<Year>, day: Day, part: Part, input: &InputSource) -> Result<(), Error> {
+    if !is_perf_available() {
+        return Err(Error::Profiler(
+            "`perf` was not found. Profiling needs `perf` on Linux, install it e.g. with `apt install linux-perf` or `dnf install perf`.".into(),
+        ));
+    }
+
+    let bin_name = get_bin_name(year, day);
+
+    let executable = limits::build_with_args(&bin_name, &["--profile", CARGO_PROFILE])
+        .map_err(Error::io("build solution"))?
+        .ok_or_else(|| Error::Build(vec![bin_name.clone()]))?;
+
+    let perf_data = env::temp_dir().join(format!("advent_of_code-{bin_name}-{part}.perf.data"));
+
+    println!("Profiling part {part} of day {day}...");
+
+    let status = Command::new("perf")
+        .args([
+            "record",
+            "--quiet",
+            "-F",
+            SAMPLE_FREQUENCY,
+            "--call-graph",
+            "dwarf",
+            "-o",
+        ])
+        .arg(&perf_data)
+        .arg("--")
+        .arg(&executable)
+        .args(["--profile", "--part", &part.to_string()])
+        .args(input.to_args())
+        .status()
+        .map_err(Error::io("run perf"))?;
+
+    if !status.success() {
+        let _ = fs::remove_file(&perf_data);
+        return Err(Error::Profiler(format!(
+            "`perf record` {}, see its output above.",
+            describe_exit(status)
+        )));
+    }
+
+    let output = Command::new("perf")
+        .arg("script")
+        .arg("-i")
+        .arg(&perf_data)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(Error::io("run perf"));
+
+    let _ = fs::remove_file(&perf_data);
+    let output = output?;
+
+    if !output.status.success() {
+        return Err(Error::Profiler(format!(
+            "`perf script` {}.",
+            describe_exit(output.status)
+        )));
+    }
+
+    let stacks = fold_stacks(&String::from_utf8_lossy(&output.stdout));
+
+    let dir = get_data_dir(year, "profiles");
+    fs::create_dir_all(&dir).map_err(Error::io("create profiles directory"))?;
+
+    let path = dir.join(format!("{day}-{part}.folded"));
+    fs::write(&path, format_stacks(&stacks)).map_err(Error::io("write profile"))?;
+
+    println!("---");
+    println!(
+        "🔥 Wrote {} samples to \"{}\".",
+        stacks.values().sum::<u64>(),
+        path.display()
+    );
+    println!(
+        "Render it as a flamegraph with e.g. `inferno-flamegraph < {} > flamegraph.svg`.",
+        path.display()
+    );
+
+    Ok(())
+}
+
+fn is_perf_available() -> bool {
+    Command::new("perf")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Folds the output of `perf script` into the number of samples per unique stack.
+/// Stacks are keyed like `02;main;_02::part_one`: the process name, then the frames from the root to the leaf.
+fn fold_stacks(script: &str) -> BTreeMap<String, u64> {
+    let mut stacks = BTreeMap::new();
+    let mut command: Option<&str> = None;
+    let mut frames: Vec<String> = vec![];
+
+    // samples are separated by empty lines, the trailing one ends the last sample.
+    for line in script.lines().chain(iter::once("")) {
+        if line.trim().is_empty() {
+            if let Some(command) = command.take() {
+                let stack: Vec<_> = iter::once(command.to_string())
+                    .chain(frames.drain(..).rev())
+                    .collect();
+                *stacks.entry(stack.join(";")).or_insert(0) += 1;
+            }
+            frames.clear();
+        } else if line.starts_with(char::is_whitespace) {
+            frames.extend(parse_frame(line));
+        } else {
+            command = line.split_whitespace().next();
+        }
+    }
+
+    stacks
+}
+
+/// Returns the symbol of a frame like `55d5c0a1b2c3 _02::part_one+0x23 (/path/to/02)`, without its offset.
+/// `;` separates frames in folded stacks, so it is replaced in symbols like `[u8; 4]`.
+fn parse_frame(line: &str) -> Option<String> {
+    let (_, frame) = line.trim().split_once(' ')?;
+    let symbol = frame.rsplit_once(" (").map_or(frame, |(symbol, _)| symbol);
+    let symbol = symbol
+        .rsplit_once("+0x")
+        .map_or(symbol, |(symbol, _)| symbol);
+    Some(symbol.replace(';', ":"))
+}
+
+fn format_stacks(stacks: &BTreeMap<String, u64>) -> String {
+    stacks
+        .iter()
+        .map(|(stack, count)| format!("{stack} {count}\n"))
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fold_stacks, format_stacks, parse_frame};
+
+    const SCRIPT: &str = "02 4242 1234.000001:    1001001 cpu-clock:u:
+\t    55d5c0a1b2c3 _02::part_one+0x23 (/target/profiling/02)
+\t    55d5c0a1b000 advent_of_code::template::runner::profile+0x40 (/target/profiling/02)
+\t    55d5c0a1a000 main+0x10 (/target/profiling/02)
+
+02 4242 1234.001002:    1001001 cpu-clock:u:
+\t    55d5c0a1b2c3 _02::part_one+0x23 (/target/profiling/02)
+\t    55d5c0a1b000 advent_of_code::template::runner::profile+0x40 (/target/profiling/02)
+\t    55d5c0a1a000 main+0x10 (/target/profiling/02)
+
+02 4242 1234.002003:    1001001 cpu-clock:u:
+\t    7f0000001000 __memcpy_avx_unaligned+0x1f (/usr/lib/libc.so.6)
+\t    55d5c0a1a000 main+0x10 (/target/profiling/02)
+";
+
+    #[test]
+    fn parses_frames() {
+        assert_eq!(
+            parse_frame("\t    55d5c0a1b2c3 _02::part_one+0x23 (/target/profiling/02)").unwrap(),
+            "_02::part_one"
+        );
+        assert_eq!(
+            parse_frame("\t    55d5c0a1b2c3 <[u8; 4] as core::fmt::Debug>::fmt+0x1 (/x)").unwrap(),
+            "<[u8: 4] as core::fmt::Debug>::fmt"
+        );
+        assert_eq!(
+            parse_frame("\t    55d5c0a1b2c3 [unknown] ([unknown])").unwrap(),
+            "[unknown]"
+        );
+    }
+
+    #[test]
+    fn folds_stacks() {
+        assert_eq!(
+            format_stacks(&fold_stacks(SCRIPT)),
+            "02;main;__memcpy_avx_unaligned 1\n02;main;advent_of_code::template::runner::profile;_02::part_one 2\n"
+        );
+    }
+}
@@ -1,16 +1,17 @@
-use std::process;
+use crate::template::aoc_client::{self, get_puzzle_path, Client};
+use crate::template::commands::Error;
+use crate::{Day, Year};
 
-use crate::template::aoc_cli;
-use crate::Day;
+pub fn handle(year: Option<Year>, day: Day) -> Result<(), Error> {
+    read(year, day).map_err(Error::Client)
+}
+
+fn read(year: Option<Year>, day: Day) -> Result<(), aoc_client::Error> {
+    let client = Client::from_env()?;
+    let puzzle = client.puzzle(year, day)?;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+    aoc_client::write_file(&get_puzzle_path(year, day), &puzzle)?;
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    println!("{puzzle}");
+    Ok(())
 }
@@ -1,19 +1,105 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
 };
 
-use crate::Day;
+use crate::template::commands::{extract, Error};
+use crate::template::{default_year, get_data_dir, get_path_for_bin, puzzle, scoped_year};
+use crate::{Day, Year};
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// The project directory user templates are loaded from, e.g. `templates/grid.rs` for `--template grid`.
+/// User templates take precedence over the built-in ones with the same name.
+const TEMPLATE_DIR: &str = "templates";
 
-pub fn part_one(input: &str) -> Option<usize> {
-    None
+const DEFAULT_TEMPLATE: &str = r#"{{solution}};
+
+pub fn part_one(_input: &str) -> Result<usize, String> {
+    Err("not implemented".into())
+}
+
+pub fn part_two(_input: &str) -> Result<usize, String> {
+    Err("not implemented".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&{{example_1}});
+        assert_eq!(result.ok(), {{example_answer_1}});
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&{{example_2}});
+        assert_eq!(result.ok(), {{example_answer_2}});
+    }
+}
+"#;
+
+const GRID_TEMPLATE: &str = r#"{{solution}};
+
+use advent_of_code::grid::Grid;
+
+fn parse(input: &str) -> Result<Grid<u8>, String> {
+    input.parse().map_err(|e| format!("{e}"))
+}
+
+pub fn part_one(input: &str) -> Result<usize, String> {
+    let _grid = parse(input)?;
+    Err("not implemented".into())
+}
+
+pub fn part_two(input: &str) -> Result<usize, String> {
+    let _grid = parse(input)?;
+    Err("not implemented".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&{{example_1}});
+        assert_eq!(result.ok(), {{example_answer_1}});
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&{{example_2}});
+        assert_eq!(result.ok(), {{example_answer_2}});
+    }
+}
+"#;
+
+const PARSER_TEMPLATE: &str = r#"{{solution}};
+
+use winnow::ascii::{dec_uint, line_ending};
+use winnow::combinator::separated;
+use winnow::{PResult, Parser};
+
+fn parse_line(input: &mut &str) -> PResult<u32> {
+    dec_uint.parse_next(input)
+}
+
+fn parse(input: &str) -> Result<Vec<u32>, String> {
+    separated(1.., parse_line, line_ending)
+        .parse(input.trim_end())
+        .map_err(|e| e.to_string())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    None
+pub fn part_one(input: &str) -> Result<usize, String> {
+    let _lines = parse(input)?;
+    Err("not implemented".into())
+}
+
+pub fn part_two(input: &str) -> Result<usize, String> {
+    let _lines = parse(input)?;
+    Err("not implemented".into())
 }
 
 #[cfg(test)]
@@ -22,73 +108,235 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_one(&{{example_1}});
+        assert_eq!(result.ok(), {{example_answer_1}});
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&{{example_2}});
+        assert_eq!(result.ok(), {{example_answer_2}});
     }
 }
 "#;
 
+const BUILT_IN_TEMPLATES: [(&str, &str); 3] = [
+    ("default", DEFAULT_TEMPLATE),
+    ("grid", GRID_TEMPLATE),
+    ("parser", PARSER_TEMPLATE),
+];
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Loads a template from the template directory, falling back to the built-in template of the same name.
+fn load_template(name: &str) -> Result<String, Error> {
+    let path = Path::new(TEMPLATE_DIR).join(format!("{name}.rs"));
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => BUILT_IN_TEMPLATES
+            .iter()
+            .find(|(built_in, _)| *built_in == name)
+            .map(|(_, template)| (*template).to_string())
+            .ok_or_else(|| {
+                let names: Vec<_> = BUILT_IN_TEMPLATES.iter().map(|(name, _)| *name).collect();
+                Error::Usage(format!(
+                    "Unknown template \"{name}\". Create \"{}\" or use one of: {}.",
+                    path.display(),
+                    names.join(", ")
+                ))
+            }),
+        Err(e) => Err(Error::IO(
+            format!("read template \"{}\"", path.display()),
+            e,
+        )),
+    }
+}
+
+/// The values of the placeholders a template can use:
+///  - `{{day}}` and `{{day_padded}}`: the day, e.g. `1` and `01`.
+///  - `{{year}}`: the year, e.g. `2023`.
+///  - `{{solution}}`: the `solution!` invocation declaring the day and, for other years than the default year, the year.
+///  - `{{title}}`: the puzzle title, if the puzzle description was downloaded.
+///  - `{{example_1}}` and `{{example_2}}`: an expression that reads the example of a part.
+///  - `{{example_answer_1}}` and `{{example_answer_2}}`: the expected example answers, as `Some(..)` or `None`.
+fn get_placeholders(
+    year: Option<Year>,
+    day: Day,
+    markdown: Option<&str>,
+) -> Vec<(&'static str, String)> {
+    let solution = match scoped_year(year) {
+        Some(year) => format!("advent_of_code::solution!({}, {year})", day.into_inner()),
+        None => format!("advent_of_code::solution!({})", day.into_inner()),
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
-        }
+    let title = markdown
+        .and_then(puzzle::title)
+        .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+
+    let examples = markdown.map(puzzle::examples).unwrap_or_default();
+    let example = |part: usize| examples.get(part).cloned().unwrap_or_default();
+
+    // part two reads its own example file only if the puzzle has a separate example for it.
+    let example_2 = match example(1).input {
+        Some(_) => "advent_of_code::template::read_year_file_part(YEAR, \"examples\", DAY, advent_of_code::part!(2))",
+        None => "advent_of_code::template::read_year_file(YEAR, \"examples\", DAY)",
+    };
+
+    // answers are only filled in if they are numbers, as the templates return numbers by default.
+    let answer = |part: usize| match example(part).answer {
+        Some(answer) if answer.parse::<i128>().is_ok() => format!("Some({answer})"),
+        _ => "None".to_string(),
+    };
+
+    vec![
+        ("day", day.into_inner().to_string()),
+        ("day_padded", day.to_string()),
+        (
+            "year",
+            year.or_else(default_year)
+                .map(|year| year.to_string())
+                .unwrap_or_default(),
+        ),
+        ("solution", solution),
+        ("title", title),
+        (
+            "example_1",
+            "advent_of_code::template::read_year_file(YEAR, \"examples\", DAY)".into(),
+        ),
+        ("example_2", example_2.into()),
+        ("example_answer_1", answer(0)),
+        ("example_answer_2", answer(1)),
+    ]
+}
+
+fn render(template: &str, placeholders: &[(&str, String)]) -> String {
+    placeholders
+        .iter()
+        .fold(template.to_string(), |s, (name, value)| {
+            s.replace(&format!("{{{{{name}}}}}"), value)
+        })
+}
+
+fn get_data_path(year: Option<Year>, folder: &str, day: Day) -> String {
+    let path = get_data_dir(year, folder).join(format!("{day}.txt"));
+    path.to_string_lossy().into_owned()
+}
+
+pub fn handle(year: Option<Year>, day: Day, template: Option<String>) -> Result<(), Error> {
+    let template = load_template(template.as_deref().unwrap_or("default"))?;
+
+    let markdown = puzzle::read(year, day);
+    let input_path = get_data_path(year, "inputs", day);
+    let example_path = get_data_path(year, "examples", day);
+    let module_path = get_path_for_bin(year, day);
+
+    for folder in ["inputs", "examples"] {
+        fs::create_dir_all(get_data_dir(year, folder))
+            .map_err(Error::io(format!("create {folder} directory")))?;
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    let mut file = safe_create_file(&module_path).map_err(Error::io("create module file"))?;
+
+    file.write_all(render(&template, &get_placeholders(year, day, markdown.as_deref())).as_bytes())
+        .map_err(Error::io("write module contents"))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    create_file(&input_path).map_err(Error::io("create input file"))?;
+    println!("Created empty input file \"{}\"", &input_path);
+
+    if let Some(markdown) = &markdown {
+        extract::write_examples(year, day, &puzzle::examples(markdown))?;
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
-        }
+    let file = create_file(&example_path).map_err(Error::io("create example file"))?;
+    if file.metadata().is_ok_and(|m| m.len() == 0) {
+        println!("Created empty example file \"{}\"", &example_path);
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    match scoped_year(year) {
+        Some(year) => println!("🎄 Type `cargo solve {day} --year {year}` to run your solution."),
+        None => println!("🎄 Type `cargo solve {day}` to run your solution."),
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{fs, path::Path, process::Command};
+
+    use super::{get_placeholders, load_template, render, BUILT_IN_TEMPLATES};
+    use crate::{day, year};
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = [
+            ("day", "1".to_string()),
+            ("title", "Trebuchet?!".to_string()),
+        ];
+        assert_eq!(
+            render(
+                "// {{title}}\nsolution!({{day}}); {{day}} {{unknown}}",
+                &placeholders
+            ),
+            "// Trebuchet?!\nsolution!(1); 1 {{unknown}}"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_built_in_templates() {
+        for (name, template) in BUILT_IN_TEMPLATES {
+            assert_eq!(load_template(name).unwrap(), template);
+        }
+        assert!(load_template("does-not-exist").is_err());
+    }
+
+    #[test]
+    fn scaffolds_solutions_without_warnings() {
+        // a crate of its own, so checking it doesn't wait on the lock of the build directory running the tests.
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let crate_dir = manifest_dir.join("target").join("scaffold-check");
+        let bin_dir = crate_dir.join("src").join("bin");
+        fs::create_dir_all(&bin_dir).unwrap();
+
+        let manifest = format!(
+            "[package]\nname = \"scaffold-check\"\nedition = \"2021\"\n\n[workspace]\n\n[dependencies]\nadvent_of_code = {{ path = {:?} }}\nwinnow = \"0.5.26\"\n",
+            manifest_dir.display().to_string()
+        );
+        fs::write(crate_dir.join("Cargo.toml"), manifest).unwrap();
+
+        let placeholders = get_placeholders(Some(year!(2015)), day!(1), None);
+        for (name, template) in BUILT_IN_TEMPLATES {
+            fs::write(
+                bin_dir.join(format!("{name}.rs")),
+                render(template, &placeholders),
+            )
+            .unwrap();
+        }
+
+        let output = Command::new("cargo")
+            .args(["clippy", "--quiet", "--all-targets", "--", "-D", "warnings"])
+            .current_dir(&crate_dir)
+            .env_remove("CARGO_TARGET_DIR")
+            .output()
+            .unwrap();
+
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
 }
@@ -1,15 +1,45 @@
 use std::process::{Command, Stdio};
 
-use crate::Day;
+use crate::template::commands::{describe_exit, Error};
+use crate::template::get_bin_name;
+use crate::template::limits::{self, Limits};
+use crate::template::runner::{InputSource, OutputFormat};
+use crate::{Day, Part, Year};
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Builds and runs the solution of a day, killing it if it exceeds one of the limits.
+/// With `count_allocations` set, the allocations of each part are reported next to its timings.
+/// Fails with [`Error::Usage`] if the input file does not exist, like `all` does for missing inputs.
+/// Fails with [`Error::Crashed`] if the solution panicked or was killed by a signal, and with [`Error::Exited`]
+/// if it exited with an error it reported itself.
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Option<Year>,
+    day: Day,
+    release: bool,
+    time: bool,
+    submit_part: Option<Part>,
+    format: OutputFormat,
+    record: bool,
+    input: &InputSource,
+    part: Option<Part>,
+    limits: Limits,
+    count_allocations: bool,
+) -> Result<(), Error> {
+    let bin_name = get_bin_name(year, day);
 
-    if release {
-        cmd_args.push("--release".to_string());
+    if let Some(path) = input.path(year, day).filter(|path| !path.exists()) {
+        return Err(match input {
+            InputSource::Puzzle => Error::missing_input(&[bin_name]),
+            _ => Error::Usage(format!("input file \"{}\" does not exist", path.display())),
+        });
     }
 
-    cmd_args.push("--".to_string());
+    // build first, so compile time does not count towards the timeout.
+    let executable = limits::build(&bin_name, release, count_allocations)
+        .map_err(Error::io("build solution"))?
+        .ok_or_else(|| Error::Build(vec![bin_name.clone()]))?;
+
+    let mut cmd_args = vec![];
 
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
@@ -20,12 +50,45 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if record {
+        cmd_args.push("--record".to_string());
+    }
+
+    cmd_args.extend(input.to_args());
+
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if format != OutputFormat::default() {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    let cmd = Command::new(executable)
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .spawn()
-        .unwrap();
+        .map_err(Error::io("run solution"))?;
+
+    let outcome = limits::supervise(cmd, limits).map_err(Error::io("run solution"))?;
+
+    if let Some(limit) = outcome.exceeded {
+        return Err(Error::Killed(vec![format!("{bin_name} ({limit})")]));
+    }
+
+    if !outcome.status.success() {
+        // the rust runtime exits with 101 on panics, other codes are errors the solution printed itself.
+        return Err(match outcome.status.code() {
+            Some(code) if code != 101 => Error::Exited(bin_name, u8::try_from(code).unwrap_or(1)),
+            _ => Error::Crashed(vec![format!(
+                "{bin_name} ({})",
+                describe_exit(outcome.status)
+            )]),
+        });
+    }
 
-    cmd.wait().unwrap();
+    Ok(())
 }
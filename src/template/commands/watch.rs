@@ -0,0 +1,243 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+use crate::template::commands::Error;
+use crate::template::runner::Record;
+use crate::template::{get_bin_name, get_data_dir, get_path_for_bin, ANSI_BOLD, ANSI_RESET};
+use crate::{Day, Year};
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Reruns the example tests and then the real input of a day whenever its solution or examples change.
+/// Runs until it is interrupted, so it only returns if the day can't be watched.
+pub fn handle(year: Option<Year>, day: Day, release: bool) -> Result<(), Error> {
+    let bin_path = get_path_for_bin(year, day);
+
+    if !PathBuf::from(&bin_path).exists() {
+        return Err(Error::Usage(format!(
+            "Module file \"{bin_path}\" does not exist. Run `cargo scaffold {day}` first."
+        )));
+    }
+
+    println!("🎄 Watching \"{bin_path}\" and the examples of day {day}. Press Ctrl+C to stop.");
+
+    let mut snapshot = get_snapshot(year, day);
+    let mut previous = run(year, day, release, &[]).unwrap_or_default();
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let current = get_snapshot(year, day);
+        if current == snapshot {
+            continue;
+        }
+
+        snapshot = current;
+        println!();
+        println!("{ANSI_BOLD}--- change detected ---{ANSI_RESET}");
+
+        if let Some(records) = run(year, day, release, &previous) {
+            previous = records;
+        }
+    }
+}
+
+/// The modification times of every watched file.
+/// Files that are created or deleted change the snapshot too.
+fn get_snapshot(year: Option<Year>, day: Day) -> BTreeMap<PathBuf, SystemTime> {
+    let mut paths = vec![PathBuf::from(get_path_for_bin(year, day))];
+
+    if let Ok(entries) = fs::read_dir(get_data_dir(year, "examples")) {
+        let prefix = day.to_string();
+        paths.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+                }),
+        );
+    }
+
+    paths
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Runs the tests and, if they pass, the real input.
+/// Returns the records of the real input, or [`None`] if the tests failed or the solution could not be run.
+fn run(year: Option<Year>, day: Day, release: bool, previous: &[Record]) -> Option<Vec<Record>> {
+    let bin_name = get_bin_name(year, day);
+
+    let mut test_args = vec!["test", "--quiet", "--bin", &bin_name];
+    if release {
+        test_args.push("--release");
+    }
+
+    let tests_passed = Command::new("cargo")
+        .args(&test_args)
+        .status()
+        .is_ok_and(|status| status.success());
+
+    if !tests_passed {
+        println!("Tests failed, skipping the real input.");
+        return None;
+    }
+
+    let mut run_args = vec!["run", "--quiet", "--bin", &bin_name];
+    if release {
+        run_args.push("--release");
+    }
+    run_args.extend(["--", "--format", "json"]);
+
+    let output = match Command::new("cargo")
+        .args(&run_args)
+        .stderr(Stdio::inherit())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("Failed to run solution: {e}");
+            return None;
+        }
+    };
+
+    let mut records = vec![];
+
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        match serde_json::from_str::<Record>(line) {
+            Ok(record) => records.push(record),
+            // anything else was printed by the solution itself.
+            Err(_) => println!("{line}"),
+        }
+    }
+
+    if !output.status.success() {
+        println!("Solution exited with {}.", output.status);
+    }
+
+    for line in format_diff(previous, &records) {
+        println!("{line}");
+    }
+
+    Some(records)
+}
+
+/// Formats one line per part, comparing answers and timings to the previous run.
+fn format_diff(previous: &[Record], latest: &[Record]) -> Vec<String> {
+    latest
+        .iter()
+        .map(|record| {
+            let prev = previous.iter().find(|p| p.part == record.part);
+            let answer = format_answer(record);
+
+            let answer = match prev.map(format_answer) {
+                Some(prev_answer) if prev_answer != answer => {
+                    format!("{answer} (was {prev_answer})")
+                }
+                _ => answer,
+            };
+
+            let duration = Duration::from_nanos(record.duration_nanos);
+
+            #[allow(clippy::cast_precision_loss)]
+            let change = match prev {
+                Some(prev) if prev.success && record.success && prev.duration_nanos > 0 => {
+                    let change = (record.duration_nanos as f64 / prev.duration_nanos as f64
+                        - 1_f64)
+                        * 100_f64;
+                    format!(" ({change:+.1}%)")
+                }
+                _ => String::new(),
+            };
+
+            format!(
+                "Part {}: {answer} {} · {duration:.1?}{change}",
+                record.part, record.verdict
+            )
+        })
+        .collect()
+}
+
+fn format_answer(record: &Record) -> String {
+    match &record.answer {
+        Some(answer) if answer.is_grid() => format!("▼\n{answer}"),
+        Some(answer) => format!("{ANSI_BOLD}{answer}{ANSI_RESET}"),
+        None => match &record.error {
+            Some(error) if error.contains('\n') => format!("✖\n{}", error.trim_end()),
+            Some(error) => format!("✖ {error}"),
+            None => "✖".into(),
+        },
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_diff;
+    use crate::template::runner::Record;
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+    use crate::{day, Part};
+
+    fn record(part: u8, answer: Option<u32>, duration_nanos: u64) -> Record {
+        Record {
+            success: answer.is_some(),
+            answer: answer.map(Into::into),
+            duration_nanos,
+            min_nanos: duration_nanos,
+            p95_nanos: duration_nanos,
+            samples: 1,
+            ..Record::empty(day!(1), Part::new(part).unwrap())
+        }
+    }
+
+    #[test]
+    fn formats_first_run() {
+        let lines = format_diff(&[], &[record(1, Some(42), 1000), record(2, None, 10)]);
+        assert_eq!(
+            lines,
+            [
+                format!("Part 1: {ANSI_BOLD}42{ANSI_RESET} ? · 1.0µs"),
+                "Part 2: ✖ ? · 10.0ns".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_changes() {
+        let previous = [record(1, Some(42), 1000), record(2, None, 10)];
+        let latest = [record(1, Some(42), 1500), record(2, Some(7), 10)];
+
+        let lines = format_diff(&previous, &latest);
+        assert_eq!(
+            lines,
+            [
+                format!("Part 1: {ANSI_BOLD}42{ANSI_RESET} ? · 1.5µs (+50.0%)"),
+                format!("Part 2: {ANSI_BOLD}7{ANSI_RESET} (was ✖) ? · 10.0ns"),
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_errors() {
+        let mut failed = record(1, None, 10);
+        failed.error = Some("no start tile".into());
+
+        let lines = format_diff(&[record(1, Some(42), 10)], &[failed]);
+        assert_eq!(
+            lines,
+            [format!(
+                "Part 1: ✖ no start tile (was {ANSI_BOLD}42{ANSI_RESET}) ? · 10.0ns"
+            )]
+        );
+    }
+}
@@ -0,0 +1,290 @@
+/// Module that runs solution binaries as child processes under optional time and memory limits.
+/// Memory is measured as peak resident set size, read from `/proc` where it is available.
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::time::{Duration, Instant};
+use std::{fs, io, thread};
+
+use serde::{Deserialize, Serialize};
+
+/// How often a running child is checked against its limits.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The limits a solution binary runs under, passed as `--timeout <duration>` and `--max-memory <size>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// The wall time a day may take, for all of its parts.
+    pub timeout: Option<Duration>,
+    /// The peak resident set size a day may reach, in bytes.
+    pub max_memory: Option<u64>,
+}
+
+impl Limits {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.timeout.is_none() && self.max_memory.is_none()
+    }
+}
+
+/// A limit that a solution exceeded before it was killed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Limit {
+    Timeout,
+    Memory,
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Limit::Timeout => f.write_str("timed out"),
+            Limit::Memory => f.write_str("out of memory"),
+        }
+    }
+}
+
+/// How a supervised child ended.
+#[derive(Debug, Clone, Copy)]
+pub struct Outcome {
+    pub status: ExitStatus,
+    /// The limit the child was killed for, [`None`] if it exited on its own.
+    pub exceeded: Option<Limit>,
+    /// The highest peak resident set size observed while the child was running.
+    pub peak_rss_bytes: Option<u64>,
+}
+
+/// Builds a solution binary and returns the path of its executable.
+/// If `is_counting_allocations` is set, the binary is built with the counting allocator, see [`crate::template::allocator`].
+/// Returns [`None`] if the build failed, compiler errors are forwarded to stderr.
+pub fn build(
+    bin_name: &str,
+    is_release: bool,
+    is_counting_allocations: bool,
+) -> io::Result<Option<PathBuf>> {
+    let mut args = vec![];
+
+    if is_release {
+        args.push("--release");
+    }
+
+    if is_counting_allocations {
+        args.extend(["--features", "count_allocations"]);
+    }
+
+    build_with_args(bin_name, &args)
+}
+
+/// Builds a solution binary with extra `cargo build` arguments, e.g. `--profile profiling`.
+/// Returns [`None`] if the build failed, see [`build`].
+pub fn build_with_args(bin_name: &str, extra_args: &[&str]) -> io::Result<Option<PathBuf>> {
+    let output = Command::new("cargo")
+        .args([
+            "build",
+            "--quiet",
+            "--bin",
+            bin_name,
+            "--message-format=json-render-diagnostics",
+        ])
+        .args(extra_args)
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .find_map(|message| {
+            let executable = message.get("executable")?.as_str()?;
+            Some(PathBuf::from(executable))
+        }))
+}
+
+/// Waits for a child to exit, killing it as soon as it exceeds one of the limits.
+pub fn supervise(mut child: Child, limits: Limits) -> io::Result<Outcome> {
+    let start = Instant::now();
+    let mut peak_rss_bytes = None;
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Outcome {
+                status,
+                exceeded: None,
+                peak_rss_bytes,
+            });
+        }
+
+        let rss = peak_rss(Some(child.id()));
+        peak_rss_bytes = peak_rss_bytes.max(rss);
+
+        let exceeded = if limits.timeout.is_some_and(|t| start.elapsed() > t) {
+            Some(Limit::Timeout)
+        } else if limits
+            .max_memory
+            .is_some_and(|max| rss.is_some_and(|rss| rss > max))
+        {
+            Some(Limit::Memory)
+        } else {
+            None
+        };
+
+        if exceeded.is_some() {
+            child.kill()?;
+
+            return Ok(Outcome {
+                status: child.wait()?,
+                exceeded,
+                peak_rss_bytes,
+            });
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Returns the peak resident set size of a process, or of the current process if no pid is given.
+/// Returns [`None`] on platforms without `/proc`.
+#[must_use]
+pub fn peak_rss(pid: Option<u32>) -> Option<u64> {
+    let path = match pid {
+        Some(pid) => format!("/proc/{pid}/status"),
+        None => "/proc/self/status".into(),
+    };
+
+    parse_peak_rss(&fs::read_to_string(path).ok()?)
+}
+
+/// Resets the peak resident set size of this process to its current size, so that [`peak_rss`] measures from now on.
+/// Returns `false` if the peak can't be reset, e.g. on platforms without `/proc` or on Linux before 4.0.
+pub fn reset_peak_rss() -> bool {
+    // writing `5` to `clear_refs` resets the high water mark, see proc(5).
+    fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+/// Reads the `VmHWM` ("high water mark") line of a `/proc/<pid>/status` file.
+fn parse_peak_rss(status: &str) -> Option<u64> {
+    let line = status.lines().find_map(|l| l.strip_prefix("VmHWM:"))?;
+    let kilobytes: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Parses a duration like `500ms`, `10s`, `1.5m` or `1h`. Plain numbers are seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration `{s}`, expecting e.g. `500ms` or `10s`"))?;
+
+    let seconds = match unit {
+        "ms" => value / 1000_f64,
+        "" | "s" => value,
+        "m" => value * 60_f64,
+        "h" => value * 3600_f64,
+        _ => {
+            return Err(format!(
+                "unknown unit `{unit}`, expecting `ms`, `s`, `m` or `h`"
+            ))
+        }
+    };
+
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("invalid duration `{s}`: {e}"))
+}
+
+/// Parses a size like `512M`, `2G` or `2GiB`. Units are binary, plain numbers are bytes.
+pub fn parse_bytes(s: &str) -> Result<u64, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+
+    let value: f64 = value
+        .parse()
+        .ok()
+        .filter(|value| *value >= 0_f64)
+        .ok_or_else(|| format!("invalid size `{s}`, expecting e.g. `512M` or `2G`"))?;
+
+    let normalized = unit.to_ascii_uppercase();
+    let factor: u64 = match normalized.trim_end_matches("IB").trim_end_matches('B') {
+        "" => 1,
+        "K" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        _ => return Err(format!("unknown unit `{unit}`, expecting `K`, `M` or `G`")),
+    };
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    Ok((value * factor as f64) as u64)
+}
+
+/// Formats a size in bytes with a binary unit, e.g. `12.3 MiB`.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1024_f64 && unit < UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, parse_bytes, parse_duration, parse_peak_rss};
+    use std::time::Duration;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("10"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_duration("1.5m"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("1h"), Ok(Duration::from_secs(3600)));
+        assert!(parse_duration("10 days").is_err());
+        assert!(parse_duration("-1s").is_err());
+        assert!(parse_duration("s").is_err());
+    }
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_bytes("512"), Ok(512));
+        assert_eq!(parse_bytes("4K"), Ok(4096));
+        assert_eq!(parse_bytes("1.5M"), Ok(1_572_864));
+        assert_eq!(parse_bytes("2GiB"), Ok(2 << 30));
+        assert_eq!(parse_bytes("2gb"), Ok(2 << 30));
+        assert!(parse_bytes("2T").is_err());
+        assert!(parse_bytes("lots").is_err());
+        assert!(parse_bytes("-1M").is_err());
+    }
+
+    #[test]
+    fn formats_sizes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(12 << 20), "12.0 MiB");
+    }
+
+    #[test]
+    fn parses_proc_status() {
+        let status = "Name:\t08\nVmPeak:\t   10000 kB\nVmHWM:\t    2048 kB\nVmRSS:\t    1024 kB\n";
+        assert_eq!(parse_peak_rss(status), Some(2 << 20));
+        assert_eq!(parse_peak_rss("Name:\t08\n"), None);
+    }
+}
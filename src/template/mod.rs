@@ -1,48 +1,273 @@
-use crate::Day;
-use std::{env, fs};
+use crate::{Day, Part, Year};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
 
-pub mod aoc_cli;
+pub mod allocator;
+pub mod answers;
+pub mod aoc_client;
+pub mod benchmark_history;
 pub mod commands;
+pub mod limits;
+pub mod puzzle;
 pub mod readme_benchmarks;
+pub mod registry;
 pub mod runner;
+pub mod submissions;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
+/// Returns the year configured via `AOC_YEAR`.
+/// Files of this year live in the top-level layout, i.e. `data/inputs/NN.txt` and `src/bin/NN.rs`.
+#[must_use]
+pub fn default_year() -> Option<Year> {
+    Year::from_env()
+}
+
+/// Returns `year` if its files live in year-scoped directories, i.e. `data/YYYY/inputs/NN.txt` and `src/bin/YYYY-NN.rs`.
+/// Returns [`None`] for the default year, which uses the top-level layout.
+#[must_use]
+pub fn scoped_year(year: Option<Year>) -> Option<Year> {
+    year.filter(|&year| Some(year) != default_year())
+}
+
+/// Returns the path of a data folder (e.g. `inputs`) for a year.
+#[must_use]
+pub fn get_data_dir(year: Option<Year>, folder: &str) -> PathBuf {
+    match scoped_year(year) {
+        Some(year) => Path::new("data").join(year.to_string()).join(folder),
+        None => Path::new("data").join(folder),
+    }
+}
+
+/// Returns the name of the solution binary of a day, e.g. `01` or `2022-01`.
+#[must_use]
+pub fn get_bin_name(year: Option<Year>, day: Day) -> String {
+    match scoped_year(year) {
+        Some(year) => format!("{year}-{day}"),
+        None => day.to_string(),
+    }
+}
+
+#[must_use]
+pub fn get_path_for_bin(year: Option<Year>, day: Day) -> String {
+    format!("./src/bin/{}.rs", get_bin_name(year, day))
+}
+
+/// Returns every year that has at least one solution in `src/bin`, in ascending order.
+/// Solutions in the top-level layout count towards the default year.
+#[must_use]
+pub fn get_years() -> Vec<Option<Year>> {
+    let Ok(entries) = fs::read_dir("src/bin") else {
+        return vec![];
+    };
+
+    let years: BTreeSet<_> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let stem = name.strip_suffix(".rs")?;
+
+            match stem.split_once('-') {
+                Some((year, day)) => {
+                    day.parse::<Day>().ok()?;
+                    Some(Some(year.parse::<Year>().ok()?))
+                }
+                None => stem.parse::<Day>().ok().map(|_| default_year()),
+            }
+        })
+        .collect();
+
+    years.into_iter().collect()
+}
+
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
+    read_year_file(None, folder, day)
+}
+
+/// Helper function that reads a text file of a year to a string.
+#[must_use]
+pub fn read_year_file(year: Option<Year>, folder: &str, day: Day) -> String {
+    let f = try_read_file(year, folder, day);
     f.expect("could not open input file")
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Helper function that reads a text file to a string, returning an error instead of panicking if it does not exist.
+pub fn try_read_file(year: Option<Year>, folder: &str, day: Day) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    let filepath = cwd
+        .join(get_data_dir(year, folder))
+        .join(format!("{day}.txt"));
+    fs::read_to_string(filepath)
+}
+
+/// Helper function that reads the text file of a puzzle part to string, e.g. `01-2.txt` for part 2.
+#[must_use]
+pub fn read_file_part(folder: &str, day: Day, part: Part) -> String {
+    read_year_file_part(None, folder, day, part)
+}
+
+/// Helper function that reads the text file of a puzzle part of a year to string, e.g. `01-2.txt` for part 2.
+#[must_use]
+pub fn read_year_file_part(year: Option<Year>, folder: &str, day: Day, part: Part) -> String {
+    read_year_file_numbered(year, folder, day, part.into_inner())
+}
+
+/// Helper function that reads a text file to string, appending a numeric suffix. E.g. like `01-3.txt`.
+/// This is for days with more examples than parts, use [`read_file_part`] for the example of a part.
+#[must_use]
+pub fn read_file_numbered(folder: &str, day: Day, number: u8) -> String {
+    read_year_file_numbered(None, folder, day, number)
+}
+
+/// Helper function that reads a text file of a year to string, appending a numeric suffix. E.g. like `01-3.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_year_file_numbered(year: Option<Year>, folder: &str, day: Day, number: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(get_data_dir(year, folder))
+        .join(format!("{day}-{number}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `YEAR` and sets up the input and runner for each part.
+/// Also registers the solution as `SOLUTION`, so it can be run in-process by `cargo all`.
+///
+/// Solutions of the default year are declared as `solution!(1)`, solutions of other years as `solution!(1, 2022)`.
+///
+/// An optional `parse` hook, e.g. `solution!(1, parse = parse)`, turns the input into a value that is
+/// passed by reference to both parts. Parsing runs once and is timed separately from the parts.
 #[macro_export]
 macro_rules! solution {
+    ($day:expr, parse = $parse:expr) => {
+        /// The year of this solution, the default year configured via `AOC_YEAR`.
+        #[allow(dead_code)]
+        const YEAR: Option<advent_of_code::Year> = None;
+
+        advent_of_code::solution!(@day $day, $parse);
+    };
+    ($day:expr, $year:expr, parse = $parse:expr) => {
+        /// The year of this solution.
+        #[allow(dead_code)]
+        const YEAR: Option<advent_of_code::Year> = Some(advent_of_code::year!($year));
+
+        advent_of_code::solution!(@day $day, $parse);
+    };
     ($day:expr) => {
+        /// The year of this solution, the default year configured via `AOC_YEAR`.
+        #[allow(dead_code)]
+        const YEAR: Option<advent_of_code::Year> = None;
+
+        advent_of_code::solution!(@day $day);
+    };
+    ($day:expr, $year:expr) => {
+        /// The year of this solution.
+        #[allow(dead_code)]
+        const YEAR: Option<advent_of_code::Year> = Some(advent_of_code::year!($year));
+
+        advent_of_code::solution!(@day $day);
+    };
+    (@day $day:expr $(, $parse:expr)?) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub struct DaySolution;
+
+        impl advent_of_code::template::registry::Solution for DaySolution {
+            fn year(&self) -> Option<advent_of_code::Year> {
+                YEAR
+            }
+
+            fn day(&self) -> advent_of_code::Day {
+                DAY
+            }
+
+            fn run_parts(
+                &self,
+                input: &str,
+                part: Option<advent_of_code::Part>,
+                is_timed: bool,
+            ) -> Vec<advent_of_code::template::runner::Record> {
+                use advent_of_code::template::runner::*;
+
+                // without a `parse` hook, both parts receive the raw input.
+                #[allow(unused_variables)]
+                let parse_stats: Option<BenchStats> = None;
+                $(
+                    let (data, stats) = time_parse($parse, input, is_timed);
+                    let (input, parse_stats) = (&data, Some(stats));
+                )?
+
+                advent_of_code::all_parts()
+                    .filter(|p| part.is_none_or(|part| part == *p))
+                    .map(|part| match part.into_inner() {
+                        1 => run_record(part_one, input, YEAR, DAY, part, is_timed, parse_stats),
+                        _ => run_record(part_two, input, YEAR, DAY, part, is_timed, parse_stats),
+                    })
+                    .collect()
+            }
+        }
+
+        /// This day's entry in the solution registry.
+        #[allow(dead_code)]
+        pub static SOLUTION: &dyn advent_of_code::template::registry::Solution = &DaySolution;
+
+        #[allow(dead_code)]
         fn main() {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let input = read_input(YEAR, DAY);
+            let input = input.as_str();
+
+            #[allow(unused_variables)]
+            let parse_stats: Option<BenchStats> = None;
+            $(
+                let (data, stats) = run_parse($parse, input);
+                let (input, parse_stats) = (&data, Some(stats));
+            )?
+
+            for part in selected_parts() {
+                match part.into_inner() {
+                    1 => run_part(part_one, input, YEAR, DAY, part, parse_stats),
+                    _ => run_part(part_two, input, YEAR, DAY, part, parse_stats),
+                }
+            }
+
+            report_peak_rss();
         }
     };
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{default_year, get_bin_name, get_data_dir, get_path_for_bin};
+    use crate::{day, year};
+    use std::path::Path;
+
+    #[test]
+    fn uses_top_level_layout_for_default_year() {
+        assert_eq!(get_bin_name(None, day!(1)), "01");
+        assert_eq!(get_bin_name(default_year(), day!(1)), "01");
+        assert_eq!(get_path_for_bin(default_year(), day!(1)), "./src/bin/01.rs");
+        assert_eq!(get_data_dir(None, "inputs"), Path::new("data/inputs"));
+    }
+
+    #[test]
+    fn scopes_paths_of_other_years() {
+        assert_eq!(get_bin_name(Some(year!(2015)), day!(1)), "2015-01");
+        assert_eq!(
+            get_path_for_bin(Some(year!(2015)), day!(25)),
+            "./src/bin/2015-25.rs"
+        );
+        assert_eq!(
+            get_data_dir(Some(year!(2015)), "inputs"),
+            Path::new("data/2015/inputs")
+        );
+    }
+}
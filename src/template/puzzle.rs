@@ -0,0 +1,160 @@
+/// Module that reads information out of puzzle descriptions saved by `cargo download`.
+/// Descriptions live in `data/puzzles/NN.md` (`data/YYYY/puzzles/NN.md` for other years).
+use std::fs;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::template::aoc_client::get_puzzle_path;
+use crate::{Day, Year};
+
+/// Reads the saved description of a day, if it was downloaded.
+#[must_use]
+pub fn read(year: Option<Year>, day: Day) -> Option<String> {
+    fs::read_to_string(get_puzzle_path(year, day)).ok()
+}
+
+/// Returns the title of a puzzle, e.g. `Trebuchet?!` for `## --- Day 1: Trebuchet?! ---`.
+#[must_use]
+pub fn title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let heading = line.strip_prefix("## --- Day ")?.strip_suffix(" ---")?;
+        let (_, title) = heading.split_once(": ")?;
+        Some(title.trim().to_string())
+    })
+}
+
+/// The example of a puzzle part.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Example {
+    /// The example input, [`None`] if the part has no example of its own.
+    pub input: Option<String>,
+    /// The answer for the example input, if it was found.
+    pub answer: Option<String>,
+}
+
+/// Extracts the examples of every part in a puzzle description.
+///
+/// The example input of a part is its first code block. Part two only gets an input of its own
+/// if its first multi-line code block differs from the example of part one.
+/// The example answer is the last emphasized code span of a part, e.g. `` `*142*` ``.
+#[must_use]
+pub fn examples(markdown: &str) -> Vec<Example> {
+    let mut sections = markdown.split("## --- Part Two ---");
+
+    let part_one = sections.next().unwrap_or_default();
+    let part_one_input = code_blocks(part_one).into_iter().next();
+
+    let mut examples = vec![Example {
+        answer: last_answer(part_one),
+        input: part_one_input.clone(),
+    }];
+
+    if let Some(part_two) = sections.next() {
+        let input = code_blocks(part_two)
+            .into_iter()
+            .find(|block| block.lines().count() > 1)
+            .filter(|block| Some(block) != part_one_input.as_ref());
+
+        examples.push(Example {
+            input,
+            answer: last_answer(part_two),
+        });
+    }
+
+    examples
+}
+
+/// Returns the contents of every fenced code block.
+fn code_blocks(markdown: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            match block.take() {
+                Some(contents) => blocks.push(contents),
+                None => block = Some(String::new()),
+            }
+        } else if let Some(contents) = &mut block {
+            contents.push_str(line);
+            contents.push('\n');
+        }
+    }
+
+    blocks
+}
+
+/// Returns the last emphasized code span, written either as `` `*42*` `` or as ``*`42`*``.
+fn last_answer(markdown: &str) -> Option<String> {
+    static ANSWER: OnceLock<Regex> = OnceLock::new();
+
+    ANSWER
+        .get_or_init(|| Regex::new(r"`\*([^`*]+)\*`|\*`([^`*]+)`\*").unwrap())
+        .captures_iter(markdown)
+        .last()
+        .and_then(|c| c.get(1).or_else(|| c.get(2)))
+        .map(|m| m.as_str().to_string())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{examples, title, Example};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+In this example, the calibration values are `12` and `38`. Adding these together produces `*50*`.
+
+## --- Part Two ---
+
+Your calculation isn't quite right.
+
+```
+two1nine
+eightwothree
+```
+
+In this example, the sum is *`281`*.
+";
+
+    #[test]
+    fn parses_title() {
+        let markdown =
+            "## --- Day 1: Trebuchet?! ---\n\nSomething is wrong.\n\n## --- Part Two ---\n";
+        assert_eq!(title(markdown).unwrap(), "Trebuchet?!");
+        assert_eq!(title("Something is wrong."), None);
+    }
+
+    #[test]
+    fn extracts_examples() {
+        assert_eq!(
+            examples(PUZZLE),
+            [
+                Example {
+                    input: Some("1abc2\npqr3stu8vwx\n".into()),
+                    answer: Some("50".into()),
+                },
+                Example {
+                    input: Some("two1nine\neightwothree\n".into()),
+                    answer: Some("281".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reuses_example_of_part_one() {
+        let puzzle = PUZZLE.replace("two1nine\neightwothree", "1abc2\npqr3stu8vwx");
+        assert_eq!(examples(&puzzle)[1].input, None);
+
+        let part_one = PUZZLE.split("## --- Part Two ---").next().unwrap();
+        assert_eq!(examples(part_one).len(), 1);
+    }
+}
@@ -1,8 +1,12 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
+use std::collections::BTreeMap;
 use std::{fs, io};
 
-use crate::Day;
+use crate::template::allocator::AllocStats;
+use crate::template::get_path_for_bin;
+use crate::template::limits::{format_bytes, Limit};
+use crate::{all_parts, Day, Part};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -21,21 +25,31 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// The formatted execution time of the `parse` hook, if the day has one.
+    pub parse: Option<String>,
+    /// The formatted execution time of every part that was solved.
+    pub parts: BTreeMap<Part, String>,
+    /// The parts that were killed because their solution exceeded a limit.
+    pub exceeded: BTreeMap<Part, Limit>,
+    /// The peak memory usage of the solution, unknown for parallel runs and on platforms without `/proc`.
+    pub peak_rss_bytes: Option<u64>,
+    /// The allocations of all parts, only known with the `count_allocations` feature.
+    pub allocations: Option<AllocStats>,
     pub total_nanos: f64,
 }
 
+impl Timings {
+    #[must_use]
+    pub fn get(&self, part: Part) -> Option<&str> {
+        self.parts.get(&part).map(String::as_str)
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -61,22 +75,47 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
 
+    // the memory column is left out if no day was measured.
+    let has_memory = timings.iter().any(|timing| timing.peak_rss_bytes.is_some());
+    let (memory_header, memory_align) = if has_memory {
+        (" Memory |", " :---: |")
+    } else {
+        ("", "")
+    };
+
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!("| Day | Parse | Part 1 | Part 2 |{memory_header} Allocations |"),
+        format!("| :---: | :---: | :---: | :---: |{memory_align} :---:  |"),
     ];
 
     for timing in timings {
-        let path = get_path_for_bin(timing.day);
+        let path = get_path_for_bin(None, timing.day);
+        let parse = timing.parse.as_deref().unwrap_or("-");
+        let parts: String = all_parts()
+            .map(|part| match timing.exceeded.get(&part) {
+                Some(limit) => format!(" {limit} |"),
+                None => format!(" `{}` |", timing.get(part).unwrap_or("-")),
+            })
+            .collect();
+        let memory = if has_memory {
+            format!(
+                " `{}` |",
+                timing.peak_rss_bytes.map_or("-".into(), format_bytes)
+            )
+        } else {
+            String::new()
+        };
+        let allocations = timing
+            .allocations
+            .map_or("-".into(), |stats| stats.to_string());
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | `{parse}` |{parts}{memory} `{allocations}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
         ));
     }
 
@@ -105,26 +144,41 @@ pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use crate::template::allocator::AllocStats;
+    use crate::template::limits::Limit;
+    use crate::{day, part};
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                parse: None,
+                parts: [(part!(1), "10ms".into()), (part!(2), "20ms".into())].into(),
+                exceeded: [].into(),
+                peak_rss_bytes: None,
+                allocations: Some(AllocStats {
+                    bytes: 2048,
+                    count: 3,
+                    peak_bytes: 1024,
+                }),
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                parse: Some("5ms".into()),
+                parts: [(part!(1), "30ms".into()), (part!(2), "40ms".into())].into(),
+                exceeded: [].into(),
+                peak_rss_bytes: Some(1_572_864),
+                allocations: None,
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                parse: None,
+                parts: [(part!(1), "40ms".into())].into(),
+                exceeded: [(part!(2), Limit::Timeout)].into(),
+                peak_rss_bytes: None,
+                allocations: None,
                 total_nanos: 9e+10,
             },
         ]
@@ -170,11 +224,11 @@ mod tests {
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 | Memory | Allocations |",
+            "| :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `-` | `10ms` | `20ms` | `-` | `2.0 KiB in 3 allocs, peak 1.0 KiB` |",
+            "| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` | `1.5 MiB` | `-` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | timed out | `-` | `-` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -183,4 +237,15 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn leaves_out_unmeasured_memory() {
+        let mut timings = get_mock_timings();
+        timings[1].peak_rss_bytes = None;
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 | Allocations |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `5ms` | `30ms` | `40ms` | `-` |"));
+    }
 }
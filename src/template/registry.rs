@@ -0,0 +1,18 @@
+/// Lets solutions be run inside of the calling process instead of as isolated binaries.
+/// Every `solution!` invocation registers its day as a [`Solution`], the `advent_of_code` binary collects these into a static table.
+/// The table is only filled if the binary is built with the `in_process` feature, see `build.rs`.
+use crate::template::runner::Record;
+use crate::{Day, Part, Year};
+
+/// A day's solution, implemented by the `solution!` macro.
+pub trait Solution: Sync {
+    /// The year this solution belongs to, [`None`] for solutions of the default year.
+    fn year(&self) -> Option<Year>;
+
+    /// The day this solution solves.
+    fn day(&self) -> Day;
+
+    /// Runs the solution against `input` without printing anything, benching it if `is_timed` is set.
+    /// Only the given part runs if one is passed. The `parse` hook, if any, runs once for all parts.
+    fn run_parts(&self, input: &str, part: Option<Part>, is_timed: bool) -> Vec<Record>;
+}
@@ -1,63 +1,565 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::allocator::{self, AllocStats};
+use crate::template::answers::{self, Verdict};
+use crate::template::limits::{self, Limit};
+use crate::template::{
+    aoc_client, default_year, get_data_dir, submissions, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::{all_parts, Answer, Day, IntoAnswer, Part, Year};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::hint::black_box;
+use std::io::{self, stdout, Read, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, process};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// How long a part is benched for with `--time`, see [`bench`].
+const BENCH_DURATION: Duration = Duration::from_secs(1);
+
+/// How long a part runs in a loop with `--profile`, see [`profile`].
+const PROFILE_DURATION: Duration = Duration::from_secs(5);
+
+pub fn run_part<I: Clone, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Option<Year>,
+    day: Day,
+    part: Part,
+    parse_stats: Option<BenchStats>,
+) {
     let part_str = format!("Part {part}");
+    let format = get_output_format();
+    let is_timed = is_timed();
+
+    // `cargo profile` samples the process while the part runs in a loop, its result is printed as usual afterwards.
+    if is_profiling() {
+        profile(&func, input.clone());
+    }
+
+    let (result, stats, allocations) = run_timed(func, input, is_timed, |result| {
+        if format == OutputFormat::Human {
+            print_result(result.as_ref().map_err(Option::as_deref), &part_str, "");
+            if is_timed {
+                print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                let _ = stdout().flush();
+            }
+        }
+    });
+
+    let mut record = Record::new(year, day, part, result, &stats, parse_stats);
+    record.allocations = allocations;
+    record.peak_rss_bytes = limits::peak_rss(None);
+
+    // recorded answers belong to the puzzle input, they say nothing about other inputs.
+    let is_puzzle_input = InputSource::from_env() == InputSource::Puzzle;
+    if !is_puzzle_input {
+        record.verdict = Verdict::Unknown;
+        record.expected = None;
+    }
+
+    print_record(&record, format);
+
+    if is_recording() {
+        if is_puzzle_input {
+            record_answer(&record);
+        } else {
+            eprintln!("Answers can only be recorded for the puzzle input.");
+        }
+    }
+
+    if let Some(result) = &record.answer {
+        submit_result(result, year, day, part);
+    }
+}
+
+/// Runs the `parse` hook of a solution and prints how long it took.
+/// The parsed input is returned so it can be passed to both parts.
+pub fn run_parse<'a, T>(parse: impl Fn(&'a str) -> T, input: &'a str) -> (T, BenchStats) {
+    let format = get_output_format();
+    let is_timed = is_timed();
+
+    if format == OutputFormat::Human && is_timed {
+        print!("Parse: > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+
+    let (data, stats) = time_parse(parse, input, is_timed);
+
+    if format == OutputFormat::Human {
+        print!("\r");
+        print_parse(&stats);
+    }
+
+    (data, stats)
+}
+
+/// Runs the `parse` hook of a solution without printing anything, benching it if `is_timed` is set.
+pub fn time_parse<'a, T>(
+    parse: impl Fn(&'a str) -> T,
+    input: &'a str,
+    is_timed: bool,
+) -> (T, BenchStats) {
+    let timer = Instant::now();
+    let data = parse(input);
+    let base_time = timer.elapsed();
+
+    let stats = if is_timed {
+        bench(&parse, input, &base_time)
+    } else {
+        BenchStats::single(base_time)
+    };
+
+    (data, stats)
+}
+
+/// Returns the parts selected with `--part`, both parts by default.
+pub fn selected_parts() -> impl Iterator<Item = Part> {
+    let args: Vec<String> = env::args().collect();
+
+    let part = args.iter().position(|x| x == "--part").map(|index| {
+        match args.get(index + 1).map(|x| x.parse::<Part>()) {
+            Some(Ok(part)) => part,
+            Some(Err(e)) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+            None => {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --part 1");
+                process::exit(1);
+            }
+        }
+    });
+
+    all_parts().filter(move |p| part.is_none_or(|part| part == *p))
+}
+
+/// Where a solution binary reads its input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input in `data/inputs`.
+    Puzzle,
+    /// An arbitrary file, passed as `--input <path>`.
+    Path(String),
+    /// Standard input, passed as `--input -` or `-`.
+    Stdin,
+    /// An example in `data/examples`, passed as `--example [N]`.
+    /// The number selects an example file with a suffix, e.g. `01-2.txt` for `--example 2`.
+    Example(Option<u8>),
+}
+
+impl InputSource {
+    /// Parses `--input <path>`, `--input -`, `-` and `--example [N]`, the puzzle input is used by default.
+    /// The parsed arguments are removed from `args`, so the caller can check the remaining ones.
+    pub fn from_args(args: &mut Vec<String>) -> Result<Self, String> {
+        if let Some(index) = args.iter().position(|x| x == "--input") {
+            let source = match args.get(index + 1).map(String::as_str) {
+                Some("-") => InputSource::Stdin,
+                Some(path) if !path.starts_with("--") => InputSource::Path(path.to_string()),
+                _ => {
+                    return Err(
+                        "Unexpected command-line input. Format: cargo solve 1 --input <path>"
+                            .into(),
+                    )
+                }
+            };
+            args.drain(index..=index + 1);
+            return Ok(source);
+        }
+
+        if let Some(index) = args.iter().position(|x| x == "-") {
+            args.remove(index);
+            return Ok(InputSource::Stdin);
+        }
+
+        if let Some(index) = args.iter().position(|x| x == "--example") {
+            let n = args.get(index + 1).and_then(|x| x.parse().ok());
+            args.drain(index..=index + usize::from(n.is_some()));
+            return Ok(InputSource::Example(n));
+        }
+
+        Ok(InputSource::Puzzle)
+    }
+
+    /// Parses the input source of this process, exits with an error message if it is invalid.
+    fn from_env() -> Self {
+        let mut args: Vec<String> = env::args().skip(1).collect();
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+        Self::from_args(&mut args).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            process::exit(1);
+        })
+    }
+
+    /// Returns the arguments that select this input, e.g. to forward them to a solution binary.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::Path(path) => vec!["--input".into(), path.clone()],
+            InputSource::Stdin => vec!["--input".into(), "-".into()],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+        }
+    }
+
+    /// Returns the file this input is read from, [`None`] for standard input.
+    #[must_use]
+    pub fn path(&self, year: Option<Year>, day: Day) -> Option<PathBuf> {
+        match self {
+            InputSource::Stdin => None,
+            InputSource::Path(path) => Some(PathBuf::from(path)),
+            InputSource::Puzzle => Some(get_data_dir(year, "inputs").join(format!("{day}.txt"))),
+            InputSource::Example(None) => {
+                Some(get_data_dir(year, "examples").join(format!("{day}.txt")))
+            }
+            InputSource::Example(Some(n)) => {
+                Some(get_data_dir(year, "examples").join(format!("{day}-{n}.txt")))
+            }
+        }
+    }
+
+    fn read(&self, year: Option<Year>, day: Day) -> Result<String, String> {
+        let Some(path) = self.path(year, day) else {
+            let mut input = String::new();
+            return io::stdin()
+                .read_to_string(&mut input)
+                .map(|_| input)
+                .map_err(|e| format!("Could not read input from stdin: {e}"));
+        };
+
+        fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read input file \"{}\": {e}", path.display()))
+    }
+}
+
+/// Reads the input selected on the command line, the puzzle input by default.
+/// Exits with an error message if the input can't be read.
+#[must_use]
+pub fn read_input(year: Option<Year>, day: Day) -> String {
+    InputSource::from_env().read(year, day).unwrap_or_else(|e| {
+        eprintln!("{e}");
+        process::exit(1);
+    })
+}
+
+/// Runs a solution part without printing anything and returns its [`Record`].
+/// This is used to run solutions in-process, see [`crate::template::registry`].
+pub fn run_record<I: Clone, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
+    input: I,
+    year: Option<Year>,
+    day: Day,
+    part: Part,
+    is_timed: bool,
+    parse_stats: Option<BenchStats>,
+) -> Record {
+    let (result, stats, allocations) = run_timed(func, input, is_timed, |_| {});
+    let mut record = Record::new(year, day, part, result, &stats, parse_stats);
+    record.allocations = allocations;
+    record
+}
+
+/// The format the runner prints results in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Colored output meant to be read in a terminal.
+    #[default]
+    Human,
+    /// One [`Record`] per line, serialized as JSON.
+    Json,
+}
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+impl FromStr for OutputFormat {
+    type Err = String;
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unknown format `{s}`, expecting `human` or `json`")),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Human => f.write_str("human"),
+            OutputFormat::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// The result of running a solution part, as emitted by `--format json`.
+/// Durations are in nanoseconds; `duration_nanos` holds the median when benching.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    /// The year of the solution, [`None`] in records written before years were tracked.
+    #[serde(default)]
+    pub year: Option<Year>,
+    pub day: Day,
+    pub part: Part,
+    pub success: bool,
+    pub answer: Option<Answer>,
+    /// The message of the error the part returned instead of an answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub duration_nanos: u64,
+    pub min_nanos: u64,
+    pub p95_nanos: u64,
+    pub stddev_nanos: u64,
+    pub samples: u64,
+    /// Whether the answer matches the one recorded in `data/answers`.
+    #[serde(default)]
+    pub verdict: Verdict,
+    #[serde(default)]
+    pub expected: Option<String>,
+    /// The median execution time of the day's `parse` hook, shared by both parts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parse_nanos: Option<u64>,
+    /// The peak memory usage of the solution process after this part, only known for isolated runs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peak_rss_bytes: Option<u64>,
+    /// The limit the solution was killed for before this part finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<Limit>,
+    /// The allocations of the first run of this part, only known with the `count_allocations` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<AllocStats>,
+}
+
+impl Record {
+    #[allow(clippy::cast_possible_truncation)]
+    fn new(
+        year: Option<Year>,
+        day: Day,
+        part: Part,
+        result: Result<Answer, Option<String>>,
+        stats: &BenchStats,
+        parse_stats: Option<BenchStats>,
+    ) -> Self {
+        let expected = answers::expected(year, day, part);
+        let (answer, error) = match result {
+            Ok(answer) => (Some(answer), None),
+            Err(error) => (None, error),
+        };
+
+        Self {
+            year: year.or_else(default_year),
+            verdict: answers::verify(answer.as_ref(), expected.as_deref()),
+            expected,
+            day,
+            part,
+            success: answer.is_some(),
+            answer,
+            error,
+            duration_nanos: stats.median.as_nanos() as u64,
+            min_nanos: stats.min.as_nanos() as u64,
+            p95_nanos: stats.p95.as_nanos() as u64,
+            stddev_nanos: stats.stddev.as_nanos() as u64,
+            samples: stats.samples as u64,
+            parse_nanos: parse_stats.map(|s| s.median.as_nanos() as u64),
+            peak_rss_bytes: None,
+            limit: None,
+            allocations: None,
+        }
+    }
+
+    /// The record of a part that did not finish because its solution exceeded a limit.
+    #[must_use]
+    pub fn exceeded(
+        year: Option<Year>,
+        day: Day,
+        part: Part,
+        limit: Limit,
+        peak_rss_bytes: Option<u64>,
+    ) -> Self {
+        Self {
+            year: year.or_else(default_year),
+            day,
+            part,
+            success: false,
+            answer: None,
+            error: None,
+            duration_nanos: 0,
+            min_nanos: 0,
+            p95_nanos: 0,
+            stddev_nanos: 0,
+            samples: 0,
+            verdict: Verdict::Unknown,
+            expected: None,
+            parse_nanos: None,
+            peak_rss_bytes,
+            limit: Some(limit),
+            allocations: None,
+        }
+    }
+
+    /// A record of an unsolved part without timings, for tests to fill in what they need with struct update syntax.
+    #[cfg(feature = "test_lib")]
+    pub(crate) fn empty(day: Day, part: Part) -> Self {
+        Self {
+            year: None,
+            day,
+            part,
+            success: false,
+            answer: None,
+            error: None,
+            duration_nanos: 0,
+            min_nanos: 0,
+            p95_nanos: 0,
+            stddev_nanos: 0,
+            samples: 0,
+            verdict: Verdict::Unknown,
+            expected: None,
+            parse_nanos: None,
+            peak_rss_bytes: None,
+            limit: None,
+            allocations: None,
+        }
+    }
+
+    #[must_use]
+    pub fn stats(&self) -> BenchStats {
+        BenchStats {
+            min: Duration::from_nanos(self.min_nanos),
+            median: Duration::from_nanos(self.duration_nanos),
+            p95: Duration::from_nanos(self.p95_nanos),
+            stddev: Duration::from_nanos(self.stddev_nanos),
+            samples: u128::from(self.samples),
+        }
+    }
+
+    /// The statistics of the day's `parse` hook. Records only keep its median.
+    #[must_use]
+    pub fn parse_stats(&self) -> Option<BenchStats> {
+        self.parse_nanos
+            .map(|nanos| BenchStats::single(Duration::from_nanos(nanos)))
+    }
+}
+
+/// Saves the answer of a record as the expected answer for its part.
+pub fn record_answer(record: &Record) {
+    let Some(answer) = &record.answer else {
+        return;
+    };
+
+    match answers::record(record.year, record.day, record.part, answer) {
+        Ok(()) => eprintln!(
+            "Recorded answer for part {} in \"{}\".",
+            record.part,
+            answers::get_path_for_answers(record.year, record.day)
+        ),
+        Err(e) => eprintln!("Failed to record answer for part {}: {e}", record.part),
+    }
+}
+
+/// Prints a [`Record`] the same way [`run_part`] prints results in the given format.
+pub fn print_record(record: &Record, format: OutputFormat) {
+    match (format, record.limit) {
+        (OutputFormat::Human, Some(limit)) => println!("Part {}: ✖ {limit}", record.part),
+        (OutputFormat::Human, None) => print_result(
+            record.answer.as_ref().ok_or(record.error.as_deref()),
+            &format!("Part {}", record.part),
+            &format!(
+                "{}{}{}",
+                format_verdict(record),
+                format_duration(&record.stats()),
+                format_allocations(record.allocations)
+            ),
+        ),
+        (OutputFormat::Json, _) => println!("{}", serde_json::to_string(record).unwrap()),
+    }
+}
+
+/// Prints how long the `parse` hook of a day took, once in front of its parts.
+pub fn print_parse(stats: &BenchStats) {
+    println!("Parse:{}", format_duration(stats));
+}
+
+/// Prints the peak memory usage of a day, once after its parts.
+pub fn print_peak_rss(bytes: u64) {
+    println!("Peak memory: {}", limits::format_bytes(bytes));
+}
+
+/// Prints the peak memory usage of this process after all parts ran, if the output is meant for humans.
+pub fn report_peak_rss() {
+    if get_output_format() == OutputFormat::Human {
+        if let Some(bytes) = limits::peak_rss(None) {
+            print_peak_rss(bytes);
+        }
+    }
+}
+
+/// Summary of the execution times collected for a solution part.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    /// The number of samples the statistics were computed from, after outlier rejection.
+    pub samples: u128,
+}
+
+impl BenchStats {
+    fn single(duration: Duration) -> Self {
+        Self {
+            min: duration,
+            median: duration,
+            p95: duration,
+            stddev: Duration::ZERO,
+            samples: 1,
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+///
+/// Allocations are only counted for the first execution, see [`allocator::measure`].
+fn run_timed<I: Clone, R: IntoAnswer>(
+    func: impl Fn(I) -> R,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+    is_timed: bool,
+    hook: impl Fn(&Result<Answer, Option<String>>),
+) -> (
+    Result<Answer, Option<String>>,
+    BenchStats,
+    Option<AllocStats>,
+) {
+    let ((result, base_time), allocations) = allocator::measure(|| {
+        let timer = Instant::now();
+        let result = func(input.clone());
+        (result, timer.elapsed())
+    });
 
+    let result = result.into_answer();
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let stats = if is_timed {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        BenchStats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats, allocations)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> BenchStats {
+    let bench_iterations = iterations(base_time, BENCH_DURATION).min(10000);
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    // warm up caches and branch predictors before collecting samples.
+    let warmup_iterations = (bench_iterations / 10).clamp(1, 100);
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    for _ in 0..warmup_iterations {
+        func(input.clone());
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,11 +571,69 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    compute_stats(timers)
+}
+
+/// Runs a part in a loop for about [`PROFILE_DURATION`], so a sampling profiler collects enough samples of it.
+/// No timings are collected, so fast parts can run millions of times without growing the memory usage.
+fn profile<I: Clone, T>(func: impl Fn(I) -> T, input: I) {
+    let timer = Instant::now();
+    black_box(func(input.clone()));
+    let base_time = timer.elapsed();
+
+    for _ in 0..iterations(&base_time, PROFILE_DURATION) {
+        black_box(func(black_box(input.clone())));
+    }
+}
+
+/// The number of times a part that took `base_time` needs to run to take about `duration`, at least 10.
+fn iterations(base_time: &Duration, duration: Duration) -> u128 {
+    (duration.as_nanos() / cmp::max(base_time.as_nanos(), 10)).max(10)
+}
+
+/// Computes summary statistics for a set of samples, discarding outliers outside of the
+/// [Tukey fences](https://en.wikipedia.org/wiki/Outlier#Tukey's_fences) first.
+fn compute_stats(mut timers: Vec<Duration>) -> BenchStats {
+    timers.sort_unstable();
+
+    let q1 = percentile(&timers, 0.25).as_nanos();
+    let q3 = percentile(&timers, 0.75).as_nanos();
+    let fence = (q3 - q1) * 3 / 2;
+    let (lower, upper) = (q1.saturating_sub(fence), q3 + fence);
+
+    timers.retain(|t| (lower..=upper).contains(&t.as_nanos()));
+
+    BenchStats {
+        min: timers[0],
+        median: percentile(&timers, 0.5),
+        p95: percentile(&timers, 0.95),
+        stddev: standard_deviation(&timers),
+        samples: timers.len() as u128,
+    }
+}
+
+/// Returns the nearest-rank percentile `p` (in range `0.0..=1.0`) of a sorted, non-empty slice.
+fn percentile(sorted: &[Duration], p: f64) -> Duration {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        clippy::cast_precision_loss
+    )]
+    let rank = (p * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn standard_deviation(numbers: &[Duration]) -> Duration {
+    let mean = average_duration(numbers) as f64;
+
+    let variance = numbers
+        .iter()
+        .map(|d| (d.as_nanos() as f64 - mean).powi(2))
+        .sum::<f64>()
+        / numbers.len() as f64;
+
+    Duration::from_secs_f64(variance.sqrt() / 1_000_000_000_f64)
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -84,20 +644,43 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+/// Formats the benchmark statistics of a part. The median is displayed first, followed by
+/// the spread of the samples, e.g. ` (74.1ns @ 9950 samples; min 70.0ns, p95 80.0ns, σ 2.1ns)`.
+fn format_duration(stats: &BenchStats) -> String {
+    let BenchStats {
+        min,
+        median,
+        p95,
+        stddev,
+        samples,
+    } = stats;
+
+    if *samples == 1 {
+        format!(" ({median:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({median:.1?} @ {samples} samples; min {min:.1?}, p95 {p95:.1?}, σ {stddev:.1?})")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Formats the allocations of a part, e.g. ` [1.2 KiB in 3 allocs, peak 1.0 KiB]`.
+fn format_allocations(allocations: Option<AllocStats>) -> String {
+    allocations.map_or(String::new(), |stats| format!(" [{stats}]"))
+}
+
+fn format_verdict(record: &Record) -> String {
+    match (&record.verdict, &record.expected) {
+        (Verdict::Fail, Some(expected)) => format!(" {} expected {expected}", record.verdict),
+        (verdict, _) => format!(" {verdict}"),
+    }
+}
+
+/// Prints the answer of a part, or `✖` followed by the error message if it has none.
+fn print_result(result: Result<&Answer, Option<&str>>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+        Ok(result) => {
+            if result.is_grid() {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -116,52 +699,202 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        // multi-line errors, e.g. of parsers pointing at the input, are printed below the part like grids.
+        Err(Some(error)) if error.contains('\n') => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
                 print!("\r");
                 println!("{part}: ✖             ");
+                println!("{}", error.trim_end());
+            }
+        }
+        Err(error) => {
+            let error = error.map(|e| format!(" {e}")).unwrap_or_default();
+            if is_intermediate_result {
+                print!("{part}: ✖{error}");
+            } else {
+                print!("\r");
+                println!("{part}: ✖{error}             ");
             }
         }
     }
 }
 
+fn is_timed() -> bool {
+    env::args().any(|x| x == "--time")
+}
+
+fn is_profiling() -> bool {
+    env::args().any(|x| x == "--profile")
+}
+
+fn is_recording() -> bool {
+    env::args().any(|x| x == "--record")
+}
+
+fn get_output_format() -> OutputFormat {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--format") else {
+        return OutputFormat::default();
+    };
+
+    match args.get(index + 1).map(|x| x.parse()) {
+        Some(Ok(format)) => format,
+        Some(Err(e)) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+        None => {
+            eprintln!("Unexpected command-line input. Format: cargo solve 1 --format json");
+            process::exit(1);
+        }
+    }
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+///  2. a session cookie is configured.
+///  3. the answer was not ruled out by a previous submission, see [`submissions::check`].
+fn submit_result(
+    result: &Answer,
+    year: Option<Year>,
     day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    part: Part,
+) -> Option<Result<aoc_client::Submission, aoc_client::Error>> {
     let args: Vec<String> = env::args().collect();
+    let part_index = args.iter().position(|x| x == "--submit")? + 1;
 
-    if !args.contains(&"--submit".into()) {
+    let Some(Ok(part_submit)) = args.get(part_index).map(|x| x.parse::<Part>()) else {
+        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+        process::exit(1);
+    };
+
+    if part_submit != part {
         return None;
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if InputSource::from_env() != InputSource::Puzzle {
+        eprintln!("Answers can only be submitted for the puzzle input.");
+        return None;
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    if result.is_grid() {
+        eprintln!("Grid answers can't be submitted as-is. Read the answer off the grid and submit it manually.");
+        return None;
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    let previous = submissions::load(year, day).unwrap_or_else(|e| {
+        eprintln!("Failed to read previous submissions: {e}");
         process::exit(1);
-    };
+    });
 
-    if part_submit != part {
+    if let Err(refusal) = submissions::check(&previous, part, result) {
+        eprintln!("Refusing to submit {result}: {refusal}");
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let client = match aoc_client::Client::from_env() {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to submit result: {e}");
+            process::exit(1);
+        }
+    };
+
+    eprintln!("Submitting result...");
+    let submission = client.submit(year, day, part, result);
+
+    match &submission {
+        Ok(submission) => {
+            eprintln!("{submission}");
+
+            if let Err(e) = submissions::record(year, day, part, result, *submission) {
+                eprintln!("Failed to record submission: {e}");
+            }
+        }
+        Err(e) => eprintln!("Failed to submit result: {e}"),
+    }
+
+    Some(submission)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compute_stats, format_duration, BenchStats, InputSource};
+    use std::time::Duration;
+
+    fn micros(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&v| Duration::from_micros(v)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = compute_stats(micros(&[5, 1, 4, 2, 3]));
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(3));
+        assert_eq!(stats.p95, Duration::from_micros(5));
+        assert_eq!(stats.stddev, Duration::from_nanos(1414));
+        assert_eq!(stats.samples, 5);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn rejects_outliers() {
+        let stats = compute_stats(micros(&[10, 11, 10, 12, 11, 10, 11, 500]));
+        assert_eq!(stats.samples, 7);
+        assert_eq!(stats.p95, Duration::from_micros(12));
+    }
+
+    #[test]
+    fn formats_single_run() {
+        let stats = BenchStats::single(Duration::from_micros(15));
+        assert_eq!(format_duration(&stats), " (15.0µs)");
+    }
+
+    #[test]
+    fn formats_bench_run() {
+        let stats = compute_stats(micros(&[5, 1, 4, 2, 3]));
+        assert_eq!(
+            format_duration(&stats),
+            " (3.0µs @ 5 samples; min 1.0µs, p95 5.0µs, σ 1.4µs)"
+        );
+    }
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_input_source() {
+        let cases = [
+            (vec![], InputSource::Puzzle),
+            (vec!["--time"], InputSource::Puzzle),
+            (
+                vec!["--input", "in.txt"],
+                InputSource::Path("in.txt".into()),
+            ),
+            (vec!["--input", "-"], InputSource::Stdin),
+            (vec!["-", "--time"], InputSource::Stdin),
+            (vec!["--example"], InputSource::Example(None)),
+            (vec!["--example", "--time"], InputSource::Example(None)),
+            (vec!["--example", "3"], InputSource::Example(Some(3))),
+        ];
+
+        for (input, expected) in cases {
+            let mut input = args(&input);
+            assert_eq!(InputSource::from_args(&mut input).unwrap(), expected);
+            assert!(input.iter().all(|x| x == "--time"), "{input:?} left over");
+        }
+
+        let mut rest = args(&["1", "--example", "2", "--time"]);
+        assert_eq!(
+            InputSource::from_args(&mut rest),
+            Ok(InputSource::Example(Some(2)))
+        );
+        assert_eq!(rest, args(&["1", "--time"]));
+
+        assert!(InputSource::from_args(&mut args(&["--input"])).is_err());
+        assert!(InputSource::from_args(&mut args(&["--input", "--time"])).is_err());
+    }
 }
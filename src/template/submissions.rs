@@ -0,0 +1,245 @@
+/// Module that keeps a log of every answer submitted to the advent of code website.
+/// The log is used to refuse submissions that are known to be wrong before they cost a lockout.
+/// Submissions live in `data/submissions/NN.txt` (`data/YYYY/submissions/NN.txt` for other years),
+/// one `<part> <outcome> <answer>` line per submission.
+use std::fmt::Display;
+use std::{fs, io};
+
+use crate::template::aoc_client::Submission;
+use crate::template::get_data_dir;
+use crate::{Answer, Day, Part, Year};
+
+/// A previous submission of a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub part: Part,
+    pub outcome: Submission,
+    pub answer: String,
+}
+
+/// The reason an answer is not submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved, with the contained answer.
+    AlreadySolved(String),
+    /// This exact answer was rejected before.
+    AlreadyRejected(Submission),
+    /// An answer lower or equal to this one was too high.
+    TooHigh(i128),
+    /// An answer higher or equal to this one was too low.
+    TooLow(i128),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with answer {answer}.")
+            }
+            Refusal::AlreadyRejected(outcome) => {
+                write!(f, "this answer was submitted before: \"{outcome}\"")
+            }
+            Refusal::TooHigh(bound) => {
+                write!(f, "{bound} was already too high, so this answer is too.")
+            }
+            Refusal::TooLow(bound) => {
+                write!(f, "{bound} was already too low, so this answer is too.")
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path_for_submissions(year: Option<Year>, day: Day) -> String {
+    get_data_dir(year, "submissions")
+        .join(format!("{day}.txt"))
+        .to_string_lossy()
+        .into_owned()
+}
+
+fn outcome_to_str(outcome: Submission) -> &'static str {
+    match outcome {
+        Submission::Correct => "correct",
+        Submission::Wrong => "wrong",
+        Submission::TooHigh => "too-high",
+        Submission::TooLow => "too-low",
+        Submission::RateLimited(_) => "rate-limited",
+        Submission::WrongLevel => "wrong-level",
+        Submission::Unknown => "unknown",
+    }
+}
+
+fn outcome_from_str(s: &str) -> Option<Submission> {
+    match s {
+        "correct" => Some(Submission::Correct),
+        "wrong" => Some(Submission::Wrong),
+        "too-high" => Some(Submission::TooHigh),
+        "too-low" => Some(Submission::TooLow),
+        "rate-limited" => Some(Submission::RateLimited(None)),
+        "wrong-level" => Some(Submission::WrongLevel),
+        "unknown" => Some(Submission::Unknown),
+        _ => None,
+    }
+}
+
+fn parse_entries(s: &str) -> Vec<Entry> {
+    s.lines()
+        .filter_map(|l| {
+            let mut fields = l.splitn(3, ' ');
+            Some(Entry {
+                part: fields.next()?.parse().ok()?,
+                outcome: outcome_from_str(fields.next()?)?,
+                answer: fields.next()?.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+fn serialize_entry(entry: &Entry) -> String {
+    format!(
+        "{} {} {}\n",
+        entry.part,
+        outcome_to_str(entry.outcome),
+        entry.answer
+    )
+}
+
+/// Reads all previous submissions of a day.
+pub fn load(year: Option<Year>, day: Day) -> Result<Vec<Entry>, io::Error> {
+    match fs::read_to_string(get_path_for_submissions(year, day)) {
+        Ok(s) => Ok(parse_entries(&s)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Appends the outcome of a submission to the log.
+pub fn record(
+    year: Option<Year>,
+    day: Day,
+    part: Part,
+    answer: &Answer,
+    outcome: Submission,
+) -> Result<(), io::Error> {
+    let path = get_path_for_submissions(year, day);
+
+    let mut contents = match fs::read_to_string(&path) {
+        Ok(s) => s,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+
+    contents.push_str(&serialize_entry(&Entry {
+        part,
+        outcome,
+        answer: answer.to_string().trim().to_string(),
+    }));
+
+    fs::create_dir_all(get_data_dir(year, "submissions"))?;
+    fs::write(path, contents)
+}
+
+/// Checks an answer against the previous submissions of its part.
+/// Answers that can't be right are refused, everything else may be submitted.
+pub fn check(entries: &[Entry], part: Part, answer: &Answer) -> Result<(), Refusal> {
+    let answer_str = answer.to_string();
+    let answer_str = answer_str.trim();
+    let entries = entries.iter().filter(|e| e.part == part);
+
+    let mut too_high: Option<i128> = None;
+    let mut too_low: Option<i128> = None;
+
+    for entry in entries {
+        match entry.outcome {
+            Submission::Correct => return Err(Refusal::AlreadySolved(entry.answer.clone())),
+            Submission::Wrong | Submission::TooHigh | Submission::TooLow
+                if entry.answer == answer_str =>
+            {
+                return Err(Refusal::AlreadyRejected(entry.outcome));
+            }
+            Submission::TooHigh => {
+                if let Ok(bound) = entry.answer.parse() {
+                    too_high = Some(too_high.map_or(bound, |b| b.min(bound)));
+                }
+            }
+            Submission::TooLow => {
+                if let Ok(bound) = entry.answer.parse() {
+                    too_low = Some(too_low.map_or(bound, |b| b.max(bound)));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Answer::Integer(value) = answer {
+        if let Some(bound) = too_high.filter(|&bound| *value >= bound) {
+            return Err(Refusal::TooHigh(bound));
+        }
+        if let Some(bound) = too_low.filter(|&bound| *value <= bound) {
+            return Err(Refusal::TooLow(bound));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, parse_entries, serialize_entry, Refusal};
+    use crate::template::aoc_client::Submission;
+    use crate::{part, Answer};
+
+    #[test]
+    fn parses_entries() {
+        let entries =
+            parse_entries("1 too-high 100\n2 wrong abc def\nnot an entry\n1 correct 42\n");
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].outcome, Submission::TooHigh);
+        assert_eq!(entries[1].part, part!(2));
+        assert_eq!(entries[1].answer, "abc def");
+        assert_eq!(serialize_entry(&entries[2]), "1 correct 42\n");
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let entries = parse_entries("1 wrong 50\n1 wrong ABC\n");
+        assert_eq!(
+            check(&entries, part!(1), &Answer::from(50)),
+            Err(Refusal::AlreadyRejected(Submission::Wrong))
+        );
+        assert_eq!(
+            check(&entries, part!(1), &Answer::from("ABC")),
+            Err(Refusal::AlreadyRejected(Submission::Wrong))
+        );
+        assert_eq!(check(&entries, part!(1), &Answer::from(51)), Ok(()));
+        assert_eq!(check(&entries, part!(2), &Answer::from(50)), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let entries =
+            parse_entries("1 too-high 100\n1 too-high 90\n1 too-low 10\n1 rate-limited 5\n");
+        assert_eq!(
+            check(&entries, part!(1), &Answer::from(95)),
+            Err(Refusal::TooHigh(90))
+        );
+        assert_eq!(
+            check(&entries, part!(1), &Answer::from(10)),
+            Err(Refusal::AlreadyRejected(Submission::TooLow))
+        );
+        assert_eq!(
+            check(&entries, part!(1), &Answer::from(5)),
+            Err(Refusal::TooLow(10))
+        );
+        assert_eq!(check(&entries, part!(1), &Answer::from(50)), Ok(()));
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let entries = parse_entries("2 too-low 1\n2 correct 42\n");
+        assert_eq!(
+            check(&entries, part!(2), &Answer::from(43)),
+            Err(Refusal::AlreadySolved("42".into()))
+        );
+    }
+}